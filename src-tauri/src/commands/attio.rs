@@ -3,8 +3,10 @@
 //! Handles saving imported Attio records as JSON files and auto-committing to Git.
 
 use crate::commands::git::git_auto_commit;
+use crate::commands::index;
+use git2::Repository;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 /// Save an Attio CRM record as a JSON file and commit it to Git.
 ///
@@ -14,6 +16,12 @@ use std::path::PathBuf;
 /// * `record_id` - Attio record UUID (for logging)
 /// * `filename` - Filename without extension (e.g., "acme-corp")
 /// * `json_content` - JSON content to write
+/// * `update_mode` - Whether this is a re-import of a previously-saved record.
+///   When `true`, the commit message is tagged `[unheard]` so `git_auto_commit`
+///   amends the prior import commit for this file in place (coalescing repeated
+///   re-imports of the same record into one commit) instead of stacking a new
+///   "Import Attio ..." commit every time; it only falls back to a new commit
+///   if `HEAD` has moved on since that import, per `git_auto_commit`'s rules.
 ///
 /// # Returns
 /// The relative file path on success (e.g., "attio/company/acme-corp.json")
@@ -25,6 +33,7 @@ pub fn save_attio_import(
     record_id: String,
     filename: String,
     json_content: String,
+    update_mode: bool,
 ) -> Result<String, String> {
     log::info!("Saving Attio import: type={object_type}, id={record_id}, file={filename}");
 
@@ -64,17 +73,43 @@ pub fn save_attio_import(
 
     log::info!("Written Attio import to {relative_path}");
 
-    // Git commit
-    let commit_message = format!("Import Attio {object_type}: {filename}");
-    match git_auto_commit(base_path, vec![relative_path.clone()], commit_message) {
+    // Git commit. Tagging the message with `[unheard]` when `update_mode` is
+    // set lets `git_auto_commit` amend the prior import of this same file
+    // instead of creating a new commit; see `update_mode`'s doc above.
+    let commit_message = if update_mode {
+        format!("[unheard] Import Attio {object_type}: {filename}")
+    } else {
+        format!("Import Attio {object_type}: {filename}")
+    };
+    let commit_hash = match git_auto_commit(
+        base_path.clone(),
+        vec![relative_path.clone()],
+        commit_message,
+        true,
+        update_mode,
+    ) {
         Ok(commit_hash) => {
             log::info!("Attio import committed: {commit_hash}");
+            Some(commit_hash)
         }
         Err(e) => {
             log::error!("Git commit failed: {e}");
             log::warn!("Attio import saved but not committed");
             // Don't fail the operation - file was saved successfully
+            None
         }
+    };
+
+    // Best-effort: indexing failures are logged but never fail the save.
+    if let Err(e) = index::index_attio_record(
+        &base_path,
+        &relative_path,
+        &object_type,
+        &record_id,
+        &json_content,
+        commit_hash.as_deref(),
+    ) {
+        log::error!("Failed to index Attio import {relative_path}: {e}");
     }
 
     Ok(relative_path)
@@ -89,24 +124,109 @@ pub struct AttioImportEntry {
     pub json_content: String,
 }
 
+/// Result of a batch Attio import, reporting how far it got.
+#[derive(Debug, Clone, serde::Serialize, specta::Type)]
+#[serde(tag = "type")]
+pub enum BatchImportOutcome {
+    /// All files were written and the Git commit succeeded.
+    Committed {
+        relative_paths: Vec<String>,
+        commit_hash: String,
+    },
+    /// All files were written, but the Git commit failed; `transactional`
+    /// was `false`, so the files were left on disk rather than rolled back.
+    SavedNotCommitted { relative_paths: Vec<String> },
+    /// `transactional` was `true` and either a write or the commit failed;
+    /// every file this batch had written was rolled back: files new to `HEAD`
+    /// were deleted and overwritten tracked files got their committed content
+    /// back.
+    RolledBack { reason: String },
+}
+
+/// Undo the files a failed transactional batch import had written so far:
+/// files that didn't exist in `HEAD` are deleted outright, and files that
+/// overwrote a tracked version are restored to their committed content.
+/// Only the paths this batch touched are affected; directories created for
+/// them are left in place.
+fn rollback_written_files(base_path: &Path, relative_paths: &[String]) {
+    let repo = Repository::open(base_path).ok();
+    let head_tree = repo
+        .as_ref()
+        .and_then(|repo| repo.head().ok())
+        .and_then(|head| head.peel_to_tree().ok());
+
+    for relative_path in relative_paths {
+        let file_path = base_path.join(relative_path);
+        let tracked_blob = head_tree
+            .as_ref()
+            .and_then(|tree| tree.get_path(Path::new(relative_path)).ok())
+            .and_then(|entry| repo.as_ref().map(|repo| (repo, entry)))
+            .and_then(|(repo, entry)| entry.to_object(repo).ok())
+            .and_then(|object| object.into_blob().ok());
+
+        let result = match tracked_blob {
+            Some(blob) => fs::write(&file_path, blob.content()),
+            None => fs::remove_file(&file_path),
+        };
+        if let Err(e) = result {
+            log::error!("Failed to roll back {relative_path}: {e}");
+        }
+    }
+
+    log::warn!("Rolled back {} file(s) from a failed batch import", relative_paths.len());
+}
+
+/// Index every entry this batch wrote, pairing each with the relative path
+/// it was saved to. Best-effort: indexing failures are logged per-entry but
+/// never fail the batch, matching `save_attio_import`'s soft-failure style.
+fn index_batch(
+    base_path: &Path,
+    imports: &[AttioImportEntry],
+    relative_paths: &[String],
+    commit_hash: Option<&str>,
+) {
+    for (entry, relative_path) in imports.iter().zip(relative_paths) {
+        if let Err(e) = index::index_attio_record(
+            base_path,
+            relative_path,
+            &entry.object_type,
+            &entry.record_id,
+            &entry.json_content,
+            commit_hash,
+        ) {
+            log::error!("Failed to index Attio import {relative_path}: {e}");
+        }
+    }
+}
+
 /// Save multiple Attio CRM records as JSON files in a single batch operation.
 ///
 /// Validates all entries first, writes all files, then creates a single Git commit.
-/// If git fails, returns the paths anyway (files are still saved).
+/// If `transactional` is `false` (the default/legacy behavior), a write or commit
+/// failure leaves whatever files were already written in place -- "saved but not
+/// committed" rather than losing the import. If `transactional` is `true`, any
+/// write or commit failure rolls back every file this batch wrote via
+/// [`rollback_written_files`]: new files are deleted and overwritten tracked
+/// files are reset to their `HEAD` content (uncommitted edits they had before
+/// the call are not restored).
 ///
 /// # Arguments
 /// * `project_path` - Path to the project root (Git repository)
 /// * `imports` - Vector of import entries to save
+/// * `transactional` - Whether to roll back all written files on any failure
+///   (all-or-nothing) instead of leaving successfully-written files in place
+///   (best-effort)
 ///
 /// # Returns
-/// A vector of relative file paths on success (e.g., ["attio/company/acme.json", ...])
+/// A [`BatchImportOutcome`] describing how far the batch got.
 #[tauri::command]
 #[specta::specta]
 pub fn batch_save_attio_imports(
     project_path: String,
     imports: Vec<AttioImportEntry>,
-) -> Result<Vec<String>, String> {
-    log::info!("Batch saving {} Attio imports", imports.len());
+    transactional: bool,
+) -> Result<BatchImportOutcome, String> {
+    log::info!("Batch saving {} Attio imports (transactional={transactional})", imports.len());
 
     if imports.is_empty() {
         return Err("No imports provided".to_string());
@@ -140,18 +260,30 @@ pub fn batch_save_attio_imports(
         // Create directory if needed
         if !dir_path.exists() {
             log::debug!("Creating attio directory: {dir_path:?}");
-            fs::create_dir_all(&dir_path).map_err(|e| {
+            if let Err(e) = fs::create_dir_all(&dir_path) {
                 log::error!("Failed to create directory: {e}");
-                format!("Failed to create directory: {e}")
-            })?;
+                if transactional {
+                    rollback_written_files(&base_path, &relative_paths);
+                    return Ok(BatchImportOutcome::RolledBack {
+                        reason: format!("Failed to create directory: {e}"),
+                    });
+                }
+                return Err(format!("Failed to create directory: {e}"));
+            }
         }
 
         // Write JSON file
         log::debug!("Writing Attio import to: {file_path:?}");
-        fs::write(&file_path, &entry.json_content).map_err(|e| {
+        if let Err(e) = fs::write(&file_path, &entry.json_content) {
             log::error!("Failed to write file: {e}");
-            format!("Failed to write file {relative_path}: {e}")
-        })?;
+            if transactional {
+                rollback_written_files(&base_path, &relative_paths);
+                return Ok(BatchImportOutcome::RolledBack {
+                    reason: format!("Failed to write file {relative_path}: {e}"),
+                });
+            }
+            return Err(format!("Failed to write file {relative_path}: {e}"));
+        }
 
         relative_paths.push(relative_path);
     }
@@ -160,18 +292,24 @@ pub fn batch_save_attio_imports(
 
     // Phase 3: Single git commit with all paths
     let commit_message = format!("Import {} Attio records", imports.len());
-    match git_auto_commit(base_path, relative_paths.clone(), commit_message) {
+    match git_auto_commit(base_path.clone(), relative_paths.clone(), commit_message, true, false) {
         Ok(commit_hash) => {
             log::info!("Batch Attio import committed: {commit_hash}");
+            index_batch(&base_path, &imports, &relative_paths, Some(&commit_hash));
+            Ok(BatchImportOutcome::Committed { relative_paths, commit_hash })
+        }
+        Err(e) if transactional => {
+            log::error!("Git commit failed: {e}");
+            rollback_written_files(&base_path, &relative_paths);
+            Ok(BatchImportOutcome::RolledBack { reason: e })
         }
         Err(e) => {
             log::error!("Git commit failed: {e}");
             log::warn!("Attio imports saved but not committed");
-            // Don't fail the operation - files were saved successfully
+            index_batch(&base_path, &imports, &relative_paths, None);
+            Ok(BatchImportOutcome::SavedNotCommitted { relative_paths })
         }
     }
-
-    Ok(relative_paths)
 }
 
 #[cfg(test)]
@@ -228,6 +366,7 @@ mod tests {
             "uuid-123".to_string(),
             "acme-corp".to_string(),
             json_content.to_string(),
+            false,
         );
 
         assert!(result.is_ok());
@@ -266,6 +405,7 @@ mod tests {
             "uuid-456".to_string(),
             "jane-doe".to_string(),
             r#"{"name":"Jane Doe"}"#.to_string(),
+            false,
         );
 
         // Should succeed even though attio directory didn't exist
@@ -287,6 +427,7 @@ mod tests {
             "uuid-123".to_string(),
             "test".to_string(),
             r#"{"name":"Test"}"#.to_string(),
+            false,
         );
 
         assert!(result.is_err());
@@ -303,6 +444,7 @@ mod tests {
             "uuid-123".to_string(),
             "".to_string(),
             r#"{"name":"Test"}"#.to_string(),
+            false,
         );
 
         assert!(result.is_err());
@@ -319,6 +461,7 @@ mod tests {
             "uuid-123".to_string(),
             "test".to_string(),
             "   ".to_string(),
+            false,
         );
 
         assert!(result.is_err());
@@ -335,6 +478,7 @@ mod tests {
             "uuid-789".to_string(),
             "top-prospects-entry-1".to_string(),
             r#"{"name":"Entry 1"}"#.to_string(),
+            false,
         );
 
         assert!(result.is_ok());
@@ -358,6 +502,7 @@ mod tests {
             "uuid-123".to_string(),
             "test".to_string(),
             r#"{"name":"Test"}"#.to_string(),
+            false,
         );
 
         // Should succeed even though Git commit fails
@@ -394,10 +539,14 @@ mod tests {
         let result = batch_save_attio_imports(
             repo_path.to_string_lossy().to_string(),
             imports,
+            false,
         );
 
-        assert!(result.is_ok());
-        let paths = result.unwrap();
+        let BatchImportOutcome::Committed { relative_paths: paths, commit_hash: _ } =
+            result.unwrap()
+        else {
+            panic!("expected BatchImportOutcome::Committed");
+        };
         assert_eq!(paths.len(), 3);
         assert_eq!(paths[0], "attio/company/acme-corp.json");
         assert_eq!(paths[1], "attio/company/globex.json");
@@ -427,6 +576,7 @@ mod tests {
         let result = batch_save_attio_imports(
             repo_path.to_string_lossy().to_string(),
             vec![],
+            false,
         );
 
         assert!(result.is_err());
@@ -445,6 +595,7 @@ mod tests {
         let result = batch_save_attio_imports(
             repo_path.to_string_lossy().to_string(),
             imports,
+            false,
         );
 
         assert!(result.is_err());
@@ -470,6 +621,7 @@ mod tests {
         let result = batch_save_attio_imports(
             repo_path.to_string_lossy().to_string(),
             imports,
+            false,
         );
 
         assert!(result.is_err());
@@ -487,14 +639,292 @@ mod tests {
         let result = batch_save_attio_imports(
             non_repo_path.to_string_lossy().to_string(),
             imports,
+            false,
         );
 
         // Should succeed (files saved) even though git fails
-        assert!(result.is_ok());
-        let paths = result.unwrap();
+        let BatchImportOutcome::SavedNotCommitted { relative_paths: paths } = result.unwrap()
+        else {
+            panic!("expected BatchImportOutcome::SavedNotCommitted");
+        };
         assert_eq!(paths.len(), 1);
 
         // Verify file was still created
         assert!(non_repo_path.join("attio/company/acme.json").exists());
     }
+
+    #[test]
+    fn test_batch_save_transactional_rolls_back_on_git_failure() {
+        let temp_dir = TempDir::new().unwrap();
+        let non_repo_path = temp_dir.path().to_path_buf();
+
+        // No git repo, so git_auto_commit fails and the transactional batch
+        // should roll back everything it wrote.
+        let imports = vec![
+            make_entry("company", "acme", "uuid-1"),
+            make_entry("person", "jane-doe", "uuid-2"),
+        ];
+
+        let result = batch_save_attio_imports(
+            non_repo_path.to_string_lossy().to_string(),
+            imports,
+            true,
+        );
+
+        let BatchImportOutcome::RolledBack { reason: _ } = result.unwrap() else {
+            panic!("expected BatchImportOutcome::RolledBack");
+        };
+
+        // Neither file should have been left behind.
+        assert!(!non_repo_path.join("attio/company/acme.json").exists());
+        assert!(!non_repo_path.join("attio/person/jane-doe.json").exists());
+    }
+
+    #[test]
+    fn test_batch_save_transactional_restores_overwritten_tracked_file() {
+        let (_temp_dir, repo_path) = create_test_repo();
+
+        // Commit an existing tracked version of one of the files the batch
+        // is about to overwrite.
+        let existing_path = repo_path.join("attio/company/acme.json");
+        fs::create_dir_all(existing_path.parent().unwrap()).unwrap();
+        fs::write(&existing_path, r#"{"id":"original","name":"acme"}"#).unwrap();
+        let repo = Repository::open(&repo_path).unwrap();
+        let mut index = repo.index().unwrap();
+        index
+            .add_path(Path::new("attio/company/acme.json"))
+            .unwrap();
+        index.write().unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let signature = Signature::now("Test User", "test@example.com").unwrap();
+        let parent = repo.head().unwrap().peel_to_commit().unwrap();
+        repo.commit(
+            Some("HEAD"),
+            &signature,
+            &signature,
+            "Track acme.json",
+            &tree,
+            &[&parent],
+        )
+        .unwrap();
+
+        // Force the commit phase to fail without touching `.git`, by
+        // requiring signing with no key configured.
+        repo.config().unwrap().set_bool("commit.gpgsign", true).unwrap();
+
+        let imports = vec![
+            make_entry("company", "acme", "uuid-new"),
+            make_entry("person", "jane-doe", "uuid-2"),
+        ];
+
+        let result = batch_save_attio_imports(
+            repo_path.to_string_lossy().to_string(),
+            imports,
+            true,
+        );
+
+        let BatchImportOutcome::RolledBack { reason } = result.unwrap() else {
+            panic!("expected BatchImportOutcome::RolledBack");
+        };
+        assert!(reason.contains("user.signingkey"));
+
+        // The newly-created file is gone, and the previously-tracked file is
+        // restored to its committed content rather than left overwritten.
+        assert!(!repo_path.join("attio/person/jane-doe.json").exists());
+        assert_eq!(
+            fs::read_to_string(&existing_path).unwrap(),
+            r#"{"id":"original","name":"acme"}"#
+        );
+    }
+
+    #[test]
+    fn test_save_attio_import_indexes_record() {
+        let (_temp_dir, repo_path) = create_test_repo();
+
+        let result = save_attio_import(
+            repo_path.to_string_lossy().to_string(),
+            "company".to_string(),
+            "uuid-123".to_string(),
+            "acme-corp".to_string(),
+            r#"{"name":"Acme Corp"}"#.to_string(),
+            false,
+        );
+        assert!(result.is_ok());
+
+        let entry = index::find_attio_record(
+            repo_path,
+            "company".to_string(),
+            "uuid-123".to_string(),
+        )
+        .unwrap()
+        .expect("record should be indexed");
+        assert_eq!(entry.relative_path, "attio/company/acme-corp.json");
+        assert_eq!(entry.name, Some("Acme Corp".to_string()));
+        assert!(entry.commit_hash.is_some());
+    }
+
+    #[test]
+    fn test_batch_save_indexes_every_entry() {
+        let (_temp_dir, repo_path) = create_test_repo();
+
+        let imports = vec![
+            make_entry("company", "acme-corp", "uuid-1"),
+            make_entry("person", "jane-doe", "uuid-2"),
+        ];
+
+        let result = batch_save_attio_imports(
+            repo_path.to_string_lossy().to_string(),
+            imports,
+            false,
+        );
+        assert!(result.is_ok());
+
+        let company = index::find_attio_record(
+            repo_path.clone(),
+            "company".to_string(),
+            "uuid-1".to_string(),
+        )
+        .unwrap()
+        .expect("company record should be indexed");
+        assert_eq!(company.name, Some("acme-corp".to_string()));
+
+        let person = index::find_attio_record(
+            repo_path,
+            "person".to_string(),
+            "uuid-2".to_string(),
+        )
+        .unwrap()
+        .expect("person record should be indexed");
+        assert_eq!(person.name, Some("jane-doe".to_string()));
+    }
+
+    #[test]
+    fn test_save_attio_import_update_mode_amends_repeated_import() {
+        let (_temp_dir, repo_path) = create_test_repo();
+
+        save_attio_import(
+            repo_path.to_string_lossy().to_string(),
+            "company".to_string(),
+            "uuid-123".to_string(),
+            "acme-corp".to_string(),
+            r#"{"name":"Acme Corp"}"#.to_string(),
+            true,
+        )
+        .unwrap();
+
+        let repo = Repository::open(&repo_path).unwrap();
+        let first_commit = repo.head().unwrap().peel_to_commit().unwrap().id();
+
+        save_attio_import(
+            repo_path.to_string_lossy().to_string(),
+            "company".to_string(),
+            "uuid-123".to_string(),
+            "acme-corp".to_string(),
+            r#"{"name":"Acme Corp (updated)"}"#.to_string(),
+            true,
+        )
+        .unwrap();
+
+        let repo = Repository::open(&repo_path).unwrap();
+        let head = repo.head().unwrap();
+        let commit = head.peel_to_commit().unwrap();
+
+        // Amended in place: still one commit on top of the initial commit,
+        // with a different id than the first import's commit.
+        assert_eq!(commit.parent_count(), 1);
+        assert_eq!(commit.parent(0).unwrap().message().unwrap(), "Initial commit");
+        assert_ne!(commit.id(), first_commit);
+        assert_eq!(
+            commit.message().unwrap(),
+            "[unheard] Import Attio company: acme-corp"
+        );
+
+        let content = fs::read_to_string(repo_path.join("attio/company/acme-corp.json")).unwrap();
+        assert_eq!(content, r#"{"name":"Acme Corp (updated)"}"#);
+    }
+
+    #[test]
+    fn test_save_attio_import_update_mode_falls_back_after_intervening_commit() {
+        let (_temp_dir, repo_path) = create_test_repo();
+
+        save_attio_import(
+            repo_path.to_string_lossy().to_string(),
+            "company".to_string(),
+            "uuid-123".to_string(),
+            "acme-corp".to_string(),
+            r#"{"name":"Acme Corp"}"#.to_string(),
+            true,
+        )
+        .unwrap();
+
+        // An unrelated commit lands on top, so the import commit is no
+        // longer HEAD and must not be rewritten.
+        save_attio_import(
+            repo_path.to_string_lossy().to_string(),
+            "person".to_string(),
+            "uuid-456".to_string(),
+            "jane-doe".to_string(),
+            r#"{"name":"Jane Doe"}"#.to_string(),
+            false,
+        )
+        .unwrap();
+
+        save_attio_import(
+            repo_path.to_string_lossy().to_string(),
+            "company".to_string(),
+            "uuid-123".to_string(),
+            "acme-corp".to_string(),
+            r#"{"name":"Acme Corp (updated)"}"#.to_string(),
+            true,
+        )
+        .unwrap();
+
+        let repo = Repository::open(&repo_path).unwrap();
+        let commit = repo.head().unwrap().peel_to_commit().unwrap();
+
+        // A new commit was created instead of amending the stale import.
+        assert_eq!(commit.parent_count(), 1);
+        assert_eq!(
+            commit.parent(0).unwrap().message().unwrap(),
+            "Import Attio person: jane-doe"
+        );
+        assert_eq!(
+            commit.message().unwrap(),
+            "[unheard] Import Attio company: acme-corp"
+        );
+    }
+
+    #[test]
+    fn test_save_attio_import_non_update_mode_keeps_legacy_message() {
+        let (_temp_dir, repo_path) = create_test_repo();
+
+        save_attio_import(
+            repo_path.to_string_lossy().to_string(),
+            "company".to_string(),
+            "uuid-123".to_string(),
+            "acme-corp".to_string(),
+            r#"{"name":"Acme Corp"}"#.to_string(),
+            false,
+        )
+        .unwrap();
+        save_attio_import(
+            repo_path.to_string_lossy().to_string(),
+            "company".to_string(),
+            "uuid-123".to_string(),
+            "acme-corp".to_string(),
+            r#"{"name":"Acme Corp (updated)"}"#.to_string(),
+            false,
+        )
+        .unwrap();
+
+        let repo = Repository::open(&repo_path).unwrap();
+        let commit = repo.head().unwrap().peel_to_commit().unwrap();
+
+        // No `[unheard]` tag, so each save stacks its own commit as before.
+        assert_eq!(commit.message().unwrap(), "Import Attio company: acme-corp");
+        assert_eq!(
+            commit.parent(0).unwrap().message().unwrap(),
+            "Import Attio company: acme-corp"
+        );
+    }
 }
@@ -1,13 +1,19 @@
-//! Chat command handlers for Claude API integration.
+//! Chat command handlers for multi-provider LLM integration.
 //!
-//! Handles streaming chat messages via the Claude API with SSE parsing.
+//! Handles streaming chat messages via a pluggable `ChatProvider` backend
+//! (Claude, OpenAI-compatible servers, Ollama) with SSE parsing. Every
+//! provider maps its own wire format onto the shared `StreamEvent`
+//! variants, so the frontend doesn't need to know which backend is active.
 
 use serde::{Deserialize, Serialize};
 use specta::Type;
+use std::collections::HashMap;
 use std::env;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, LazyLock, Mutex};
 use tauri::ipc::Channel;
 
-/// Maximum timeout for Claude API requests (60 seconds)
+/// Maximum timeout for chat API requests (60 seconds)
 const CLAUDE_API_TIMEOUT_SECS: u64 = 60;
 
 /// Claude API endpoint
@@ -16,12 +22,37 @@ const CLAUDE_API_URL: &str = "https://api.anthropic.com/v1/messages";
 /// Claude API version header
 const ANTHROPIC_VERSION: &str = "2023-06-01";
 
-/// Default model to use
+/// Default Claude model to use
 const DEFAULT_MODEL: &str = "claude-sonnet-4-5-20250929";
 
 /// Default max tokens
 const DEFAULT_MAX_TOKENS: u32 = 4096;
 
+/// Maximum number of send attempts for a single chat request (1 initial + retries)
+const MAX_RETRY_ATTEMPTS: u32 = 3;
+
+/// Base delay for exponential backoff when a provider gives no `retry-after`
+const RETRY_BASE_DELAY_SECS: u64 = 2;
+
+/// Upper bound on computed backoff delay, regardless of attempt count
+const RETRY_MAX_DELAY_SECS: u64 = 30;
+
+/// Default OpenAI endpoint
+const OPENAI_API_URL: &str = "https://api.openai.com/v1/chat/completions";
+
+/// Default OpenAI model
+const DEFAULT_OPENAI_MODEL: &str = "gpt-4o-mini";
+
+/// Default Ollama model
+const DEFAULT_OLLAMA_MODEL: &str = "llama3";
+
+/// Ollama's OpenAI-compatible chat endpoint. Overridable via `OLLAMA_HOST`
+/// for users running it on a non-default host/port.
+fn ollama_api_url() -> String {
+    let host = env::var("OLLAMA_HOST").unwrap_or_else(|_| "http://localhost:11434".to_string());
+    format!("{}/v1/chat/completions", host.trim_end_matches('/'))
+}
+
 // ============================================================================
 // Types
 // ============================================================================
@@ -30,7 +61,73 @@ const DEFAULT_MAX_TOKENS: u32 = 4096;
 #[derive(Debug, Clone, Serialize, Deserialize, Type)]
 pub struct ChatMessage {
     pub role: String,
-    pub content: String,
+    pub content: MessageContent,
+}
+
+/// A single part of a multimodal message: plain text or an inline image.
+/// Mirrors Anthropic's content-block shapes.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ContentPart {
+    Text { text: String },
+    Image { source: ImageSource },
+}
+
+/// An inline, base64-encoded image, in the shape Anthropic's API expects.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct ImageSource {
+    #[serde(rename = "type")]
+    pub source_type: String,
+    pub media_type: String,
+    pub data: String,
+}
+
+/// Message content: either a plain string or a list of content parts
+/// (text/image). Deserializes from either shape; when serializing, a
+/// single text part collapses back down to a plain string so existing
+/// text-only consumers see no change.
+#[derive(Debug, Clone, Deserialize, Type)]
+#[serde(untagged)]
+pub enum MessageContent {
+    Text(String),
+    Parts(Vec<ContentPart>),
+}
+
+impl MessageContent {
+    pub fn from_text(text: impl Into<String>) -> Self {
+        MessageContent::Text(text.into())
+    }
+
+    /// Flattens to plain text, concatenating any text parts and dropping
+    /// images. Used by providers that don't (yet) support multimodal input.
+    pub fn as_plain_text(&self) -> String {
+        match self {
+            MessageContent::Text(text) => text.clone(),
+            MessageContent::Parts(parts) => parts
+                .iter()
+                .filter_map(|part| match part {
+                    ContentPart::Text { text } => Some(text.as_str()),
+                    ContentPart::Image { .. } => None,
+                })
+                .collect::<Vec<_>>()
+                .join("\n"),
+        }
+    }
+}
+
+impl Serialize for MessageContent {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        match self {
+            MessageContent::Text(text) => serializer.serialize_str(text),
+            MessageContent::Parts(parts) => match parts.as_slice() {
+                [ContentPart::Text { text }] => serializer.serialize_str(text),
+                _ => parts.serialize(serializer),
+            },
+        }
+    }
 }
 
 /// Response from the chat command
@@ -82,14 +179,126 @@ impl std::fmt::Display for ChatError {
 #[serde(tag = "type")]
 pub enum StreamEvent {
     Token { content: String },
-    Done,
+    /// A tool-use content block has started (Claude only)
+    ToolUseStart { id: String, name: String },
+    /// A tool-use content block finished streaming; `input` is the fully
+    /// assembled JSON arguments (Claude only)
+    ToolUseInput { id: String, input: serde_json::Value },
+    /// Token accounting for the request so far (Claude only)
+    Usage { input_tokens: u32, output_tokens: u32 },
+    /// `stop_reason` is `None` for providers that don't report one
+    /// (e.g. `end_turn`, `max_tokens`, `tool_use` for Claude)
+    Done { stop_reason: Option<String> },
+    /// The request was cancelled via `cancel_chat_message` before completion
+    Cancelled,
+    /// A rate limit or transient server error is being retried with backoff;
+    /// only ever emitted before the first `Token`, so the frontend can show
+    /// "retrying in Ns" without risking duplicated output
+    Retrying { attempt: u32, delay_secs: u64 },
     Error { message: String },
 }
 
+/// Which backend `send_chat_message` should drive. Defaults to `Claude` so
+/// existing callers that don't pass a provider keep their current behavior.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum Provider {
+    #[default]
+    Claude,
+    OpenAi,
+    Ollama,
+}
+
 // ============================================================================
-// API Request/Response Types (internal)
+// Provider abstraction
 // ============================================================================
 
+/// Provider-agnostic request parameters, built once by `send_chat_message`
+/// and handed to whichever `ChatProvider` is selected.
+struct ChatRequestParams {
+    model: String,
+    max_tokens: u32,
+    messages: Vec<ChatMessage>,
+    system_prompt: Option<String>,
+    tools: Option<Vec<ToolDefinition>>,
+    temperature: Option<f32>,
+    top_p: Option<f32>,
+    stop_sequences: Option<Vec<String>>,
+    /// Extended thinking token budget (Claude only; ignored by other providers)
+    thinking_budget: Option<u32>,
+}
+
+/// Per-turn overrides for model, sampling, and thinking budget; any field
+/// left `None` falls back to the provider's default. aichat drives all of
+/// these from per-model config; here the frontend sends them per-turn
+/// instead, so different conversations can use different models without
+/// recompiling.
+#[derive(Debug, Clone, Default, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ChatOptions {
+    pub model: Option<String>,
+    pub max_tokens: Option<u32>,
+    pub temperature: Option<f32>,
+    pub top_p: Option<f32>,
+    pub stop_sequences: Option<Vec<String>>,
+    /// Extended thinking token budget (Claude only; ignored by other providers)
+    pub thinking_budget: Option<u32>,
+}
+
+/// A chat backend: knows how to build its own request body, authenticate,
+/// and translate its SSE wire format onto the shared `StreamEvent` variants.
+///
+/// Modeled after aichat's single `SendData`/`Message` core dispatched to
+/// per-provider modules — the streaming command and frontend stay the same
+/// regardless of which `ChatProvider` is plugged in.
+trait ChatProvider {
+    /// Endpoint to POST the streaming request to.
+    fn endpoint(&self) -> String;
+
+    /// Headers needed for authentication, beyond `content-type`.
+    fn auth_headers(&self) -> Result<Vec<(String, String)>, ChatError>;
+
+    /// Serialize the request body in this provider's wire format.
+    fn build_request(&self, params: &ChatRequestParams) -> serde_json::Value;
+
+    /// Translate one decoded SSE `data:` payload into a `StreamEvent`, if
+    /// it maps to one (providers emit bookkeeping events we can ignore).
+    /// Takes `&mut self` because Claude's tool-use blocks stream as
+    /// fragments that must be accumulated across calls.
+    fn parse_sse_event(&mut self, data: &str) -> Result<Option<StreamEvent>, ChatError>;
+}
+
+/// A tool Claude may call, in the shape the Messages API expects
+/// (`name`, `description`, JSON Schema `input_schema`).
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct ToolDefinition {
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    pub input_schema: serde_json::Value,
+}
+
+/// A tool-use content block whose `input_json_delta` fragments are still
+/// being accumulated.
+#[derive(Debug, Default)]
+struct PendingToolUse {
+    id: String,
+    name: String,
+    partial_json: String,
+}
+
+/// Anthropic Claude, via the native Messages API.
+#[derive(Default)]
+struct ClaudeProvider {
+    /// Tool-use blocks currently streaming, keyed by content block index.
+    pending_tool_use: std::collections::HashMap<usize, PendingToolUse>,
+    /// Input token count from `message_start`, remembered so it can be
+    /// reported alongside the output token count on `message_delta`.
+    last_input_tokens: Option<u32>,
+    /// Stop reason from `message_delta`, attached to `Done` once `message_stop` arrives.
+    pending_stop_reason: Option<String>,
+}
+
 #[derive(Debug, Serialize)]
 struct ClaudeRequest {
     model: String,
@@ -98,6 +307,24 @@ struct ClaudeRequest {
     stream: bool,
     #[serde(skip_serializing_if = "Option::is_none")]
     system: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tools: Option<Vec<ToolDefinition>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    temperature: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    top_p: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    stop_sequences: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    thinking: Option<ClaudeThinking>,
+}
+
+/// Extended thinking budget, in the shape the Messages API expects.
+#[derive(Debug, Serialize)]
+struct ClaudeThinking {
+    #[serde(rename = "type")]
+    thinking_type: &'static str,
+    budget_tokens: u32,
 }
 
 #[derive(Debug, Deserialize)]
@@ -105,15 +332,320 @@ struct ClaudeEvent {
     #[serde(rename = "type")]
     event_type: String,
     #[serde(default)]
+    index: Option<usize>,
+    #[serde(default)]
+    content_block: Option<ContentBlockStart>,
+    #[serde(default)]
     delta: Option<DeltaContent>,
+    /// Present on `message_start`, carries the prompt's input token count.
+    #[serde(default)]
+    message: Option<MessageStartInfo>,
+    /// Present on `message_delta`, carries the final output token count.
+    #[serde(default)]
+    usage: Option<UsageInfo>,
 }
 
 #[derive(Debug, Deserialize)]
-struct DeltaContent {
+struct ContentBlockStart {
     #[serde(rename = "type")]
+    block_type: String,
+    #[serde(default)]
+    id: Option<String>,
+    #[serde(default)]
+    name: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct DeltaContent {
+    /// Content-block deltas always have this; `message_delta`'s top-level
+    /// `delta` object doesn't, hence the default.
+    #[serde(default, rename = "type")]
     delta_type: String,
     #[serde(default)]
     text: Option<String>,
+    #[serde(default)]
+    partial_json: Option<String>,
+    /// Only present on `message_delta`.
+    #[serde(default)]
+    stop_reason: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct MessageStartInfo {
+    usage: UsageInfo,
+}
+
+#[derive(Debug, Deserialize)]
+struct UsageInfo {
+    #[serde(default)]
+    input_tokens: Option<u32>,
+    #[serde(default)]
+    output_tokens: Option<u32>,
+}
+
+impl ChatProvider for ClaudeProvider {
+    fn endpoint(&self) -> String {
+        CLAUDE_API_URL.to_string()
+    }
+
+    fn auth_headers(&self) -> Result<Vec<(String, String)>, ChatError> {
+        let api_key = env::var("ANTHROPIC_API_KEY").map_err(|_| ChatError::ConfigError {
+            message: "ANTHROPIC_API_KEY not configured".to_string(),
+        })?;
+        Ok(vec![
+            ("anthropic-version".to_string(), ANTHROPIC_VERSION.to_string()),
+            ("x-api-key".to_string(), api_key),
+        ])
+    }
+
+    fn build_request(&self, params: &ChatRequestParams) -> serde_json::Value {
+        serde_json::to_value(ClaudeRequest {
+            model: params.model.clone(),
+            max_tokens: params.max_tokens,
+            messages: params.messages.clone(),
+            stream: true,
+            system: params.system_prompt.clone(),
+            tools: params.tools.clone(),
+            temperature: params.temperature,
+            top_p: params.top_p,
+            stop_sequences: params.stop_sequences.clone(),
+            thinking: params.thinking_budget.map(|budget_tokens| ClaudeThinking {
+                thinking_type: "enabled",
+                budget_tokens,
+            }),
+        })
+        .expect("ClaudeRequest always serializes")
+    }
+
+    fn parse_sse_event(&mut self, data: &str) -> Result<Option<StreamEvent>, ChatError> {
+        let event: ClaudeEvent = serde_json::from_str(data).map_err(|e| ChatError::ParseError {
+            message: format!("Failed to parse Claude event: {e}"),
+        })?;
+
+        match event.event_type.as_str() {
+            "content_block_start" => {
+                if let Some(block) = event.content_block {
+                    if block.block_type == "tool_use" {
+                        let index = event.index.unwrap_or_default();
+                        let id = block.id.unwrap_or_default();
+                        let name = block.name.unwrap_or_default();
+                        self.pending_tool_use.insert(
+                            index,
+                            PendingToolUse {
+                                id: id.clone(),
+                                name: name.clone(),
+                                partial_json: String::new(),
+                            },
+                        );
+                        return Ok(Some(StreamEvent::ToolUseStart { id, name }));
+                    }
+                }
+                Ok(None)
+            }
+            "content_block_delta" => {
+                let Some(delta) = event.delta else {
+                    return Ok(None);
+                };
+                match delta.delta_type.as_str() {
+                    "text_delta" => Ok(delta.text.map(|text| StreamEvent::Token { content: text })),
+                    "input_json_delta" => {
+                        if let (Some(index), Some(fragment)) = (event.index, delta.partial_json) {
+                            if let Some(pending) = self.pending_tool_use.get_mut(&index) {
+                                pending.partial_json.push_str(&fragment);
+                            }
+                        }
+                        Ok(None)
+                    }
+                    _ => Ok(None),
+                }
+            }
+            "content_block_stop" => {
+                let index = event.index.unwrap_or_default();
+                let Some(pending) = self.pending_tool_use.remove(&index) else {
+                    // Plain text block closing, not a tool-use block.
+                    return Ok(None);
+                };
+                let input = if pending.partial_json.trim().is_empty() {
+                    serde_json::json!({})
+                } else {
+                    serde_json::from_str(&pending.partial_json).map_err(|e| {
+                        ChatError::ParseError {
+                            message: format!("Failed to parse tool input JSON: {e}"),
+                        }
+                    })?
+                };
+                Ok(Some(StreamEvent::ToolUseInput {
+                    id: pending.id,
+                    input,
+                }))
+            }
+            "message_start" => {
+                if let Some(message) = event.message {
+                    self.last_input_tokens = message.usage.input_tokens;
+                }
+                Ok(None)
+            }
+            "message_delta" => {
+                if let Some(delta) = &event.delta {
+                    if delta.stop_reason.is_some() {
+                        self.pending_stop_reason = delta.stop_reason.clone();
+                    }
+                }
+                let Some(output_tokens) = event.usage.and_then(|usage| usage.output_tokens) else {
+                    return Ok(None);
+                };
+                Ok(Some(StreamEvent::Usage {
+                    input_tokens: self.last_input_tokens.unwrap_or(0),
+                    output_tokens,
+                }))
+            }
+            "message_stop" => Ok(Some(StreamEvent::Done {
+                stop_reason: self.pending_stop_reason.take(),
+            })),
+            _ => Ok(None),
+        }
+    }
+}
+
+/// OpenAI's `/v1/chat/completions` streaming format, also used verbatim by
+/// Ollama's OpenAI-compatibility endpoint.
+#[derive(Debug, Deserialize)]
+struct OpenAiChunk {
+    choices: Vec<OpenAiChoice>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OpenAiChoice {
+    #[serde(default)]
+    delta: OpenAiDelta,
+    #[serde(default)]
+    finish_reason: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct OpenAiDelta {
+    #[serde(default)]
+    content: Option<String>,
+}
+
+/// Shared parsing for any OpenAI-compatible SSE chunk.
+fn parse_openai_compatible_event(data: &str) -> Result<Option<StreamEvent>, ChatError> {
+    let chunk: OpenAiChunk = serde_json::from_str(data).map_err(|e| ChatError::ParseError {
+        message: format!("Failed to parse chat completion chunk: {e}"),
+    })?;
+
+    let Some(choice) = chunk.choices.into_iter().next() else {
+        return Ok(None);
+    };
+
+    if let Some(content) = choice.delta.content {
+        if !content.is_empty() {
+            return Ok(Some(StreamEvent::Token { content }));
+        }
+    }
+
+    if let Some(finish_reason) = choice.finish_reason {
+        return Ok(Some(StreamEvent::Done {
+            stop_reason: Some(finish_reason),
+        }));
+    }
+
+    Ok(None)
+}
+
+/// Any OpenAI-compatible server (api.openai.com, Azure OpenAI, local
+/// proxies, etc.), authenticated with a bearer token.
+struct OpenAiProvider;
+
+impl ChatProvider for OpenAiProvider {
+    fn endpoint(&self) -> String {
+        OPENAI_API_URL.to_string()
+    }
+
+    fn auth_headers(&self) -> Result<Vec<(String, String)>, ChatError> {
+        let api_key = env::var("OPENAI_API_KEY").map_err(|_| ChatError::ConfigError {
+            message: "OPENAI_API_KEY not configured".to_string(),
+        })?;
+        Ok(vec![("authorization".to_string(), format!("Bearer {api_key}"))])
+    }
+
+    fn build_request(&self, params: &ChatRequestParams) -> serde_json::Value {
+        build_openai_compatible_request(params)
+    }
+
+    fn parse_sse_event(&mut self, data: &str) -> Result<Option<StreamEvent>, ChatError> {
+        parse_openai_compatible_event(data)
+    }
+}
+
+/// A local Ollama instance, reached via its OpenAI-compatibility endpoint.
+/// No API key is required by default.
+struct OllamaProvider;
+
+impl ChatProvider for OllamaProvider {
+    fn endpoint(&self) -> String {
+        ollama_api_url()
+    }
+
+    fn auth_headers(&self) -> Result<Vec<(String, String)>, ChatError> {
+        Ok(Vec::new())
+    }
+
+    fn build_request(&self, params: &ChatRequestParams) -> serde_json::Value {
+        build_openai_compatible_request(params)
+    }
+
+    fn parse_sse_event(&mut self, data: &str) -> Result<Option<StreamEvent>, ChatError> {
+        parse_openai_compatible_event(data)
+    }
+}
+
+/// Build an OpenAI-shaped chat completion request, prepending the system
+/// prompt as a `system` message (OpenAI-compatible APIs have no separate
+/// `system` field the way Claude's Messages API does).
+fn build_openai_compatible_request(params: &ChatRequestParams) -> serde_json::Value {
+    let mut messages = Vec::with_capacity(params.messages.len() + 1);
+    if let Some(system_prompt) = &params.system_prompt {
+        messages.push(serde_json::json!({ "role": "system", "content": system_prompt }));
+    }
+    for message in &params.messages {
+        // Images aren't supported by the OpenAI-compatible wire format this
+        // helper builds for, so fall back to the concatenated text parts.
+        messages.push(serde_json::json!({ "role": message.role, "content": message.content.as_plain_text() }));
+    }
+
+    let mut body = serde_json::json!({
+        "model": params.model,
+        "max_tokens": params.max_tokens,
+        "messages": messages,
+        "stream": true,
+    });
+    if let Some(temperature) = params.temperature {
+        body["temperature"] = serde_json::json!(temperature);
+    }
+    if let Some(top_p) = params.top_p {
+        body["top_p"] = serde_json::json!(top_p);
+    }
+    if let Some(stop_sequences) = &params.stop_sequences {
+        body["stop"] = serde_json::json!(stop_sequences);
+    }
+    body
+}
+
+fn make_provider(provider: Provider) -> Box<dyn ChatProvider + Send + Sync> {
+    match provider {
+        Provider::Claude => Box::new(ClaudeProvider::default()),
+        Provider::OpenAi => Box::new(OpenAiProvider),
+        Provider::Ollama => Box::new(OllamaProvider),
+    }
+}
+
+fn default_model_for(provider: Provider) -> &'static str {
+    match provider {
+        Provider::Claude => DEFAULT_MODEL,
+        Provider::OpenAi => DEFAULT_OPENAI_MODEL,
+        Provider::Ollama => DEFAULT_OLLAMA_MODEL,
+    }
 }
 
 // ============================================================================
@@ -127,43 +659,138 @@ fn emit_error(channel: &Channel<StreamEvent>, error: &ChatError) {
     let _ = channel.send(StreamEvent::Error { message });
 }
 
+/// Exponential backoff delay for retry `attempt` (1-based), doubling from
+/// `RETRY_BASE_DELAY_SECS` and capped at `RETRY_MAX_DELAY_SECS`, with up to
+/// one second of jitter so concurrent requests don't retry in lockstep.
+/// There's no `rand` dependency in this crate, so jitter is sourced from the
+/// low bits of the current time instead of pulling one in.
+fn backoff_delay_secs(attempt: u32) -> u64 {
+    let exponential = RETRY_BASE_DELAY_SECS.saturating_mul(1u64 << attempt.saturating_sub(1));
+    let capped = exponential.min(RETRY_MAX_DELAY_SECS);
+    let jitter = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| u64::from(d.subsec_nanos() % 2))
+        .unwrap_or(0);
+    (capped + jitter).min(RETRY_MAX_DELAY_SECS)
+}
+
+/// Cancellation flags for in-flight `send_chat_message` calls, keyed by the
+/// caller-supplied request id. Cleared once the request finishes, whether it
+/// completed, errored, or was cancelled.
+static CANCEL_TOKENS: LazyLock<Mutex<HashMap<String, Arc<AtomicBool>>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Registers a fresh cancellation flag for `request_id`, overwriting any
+/// stale entry left behind by a request id that was reused.
+fn register_cancel_token(request_id: &str) -> Arc<AtomicBool> {
+    let token = Arc::new(AtomicBool::new(false));
+    CANCEL_TOKENS
+        .lock()
+        .expect("cancel token registry lock poisoned")
+        .insert(request_id.to_string(), token.clone());
+    token
+}
+
+fn clear_cancel_token(request_id: &str) {
+    CANCEL_TOKENS
+        .lock()
+        .expect("cancel token registry lock poisoned")
+        .remove(request_id);
+}
+
+/// Requests cancellation of an in-flight `send_chat_message` call.
+///
+/// Returns `true` if a matching in-flight request was found and flagged,
+/// `false` if `request_id` doesn't match anything (already finished, or
+/// never started).
+#[tauri::command]
+#[specta::specta]
+pub fn cancel_chat_message(request_id: String) -> bool {
+    match CANCEL_TOKENS
+        .lock()
+        .expect("cancel token registry lock poisoned")
+        .get(&request_id)
+    {
+        Some(token) => {
+            token.store(true, Ordering::SeqCst);
+            true
+        }
+        None => false,
+    }
+}
+
 // ============================================================================
 // Command
 // ============================================================================
 
-/// Sends a chat message to Claude API with streaming responses.
+/// Sends a chat message to the selected provider with streaming responses.
 ///
 /// # Arguments
-/// * `message` - The user's message
+/// * `message` - The user's message; plain text or multimodal content parts
 /// * `history` - Previous conversation messages
 /// * `system_prompt` - Optional system prompt for context
+/// * `provider` - Which backend to drive (defaults to `Provider::Claude`)
+/// * `tools` - Tool definitions Claude may call (ignored by other providers)
+/// * `options` - Optional overrides for model, sampling, and thinking budget
+/// * `request_id` - Caller-chosen id; pass to `cancel_chat_message` to abort this call
 /// * `channel` - Tauri channel for streaming token events
 ///
 /// # Streaming Events
 /// - `StreamEvent::Token { content }` - Each token as received
-/// - `StreamEvent::Done` - Stream completed successfully
+/// - `StreamEvent::ToolUseStart { id, name }` - Claude started a tool call
+/// - `StreamEvent::ToolUseInput { id, input }` - Claude finished a tool call's arguments
+/// - `StreamEvent::Usage { input_tokens, output_tokens }` - Token accounting (Claude only)
+/// - `StreamEvent::Done { stop_reason }` - Stream completed successfully
+/// - `StreamEvent::Cancelled` - Stream was stopped via `cancel_chat_message`
+/// - `StreamEvent::Retrying { attempt, delay_secs }` - A rate limit or server
+///   error is being retried before any token has streamed
 /// - `StreamEvent::Error { message }` - Error occurred during streaming
 ///
 /// # Errors
-/// Returns `ChatError` for API failures, configuration issues, or network errors.
+/// Returns `ChatError` for API failures, configuration issues, or network errors
+/// once `MAX_RETRY_ATTEMPTS` is exhausted.
 #[tauri::command]
 #[specta::specta]
 pub async fn send_chat_message(
-    message: String,
+    message: MessageContent,
     history: Vec<ChatMessage>,
     system_prompt: Option<String>,
+    provider: Option<Provider>,
+    tools: Option<Vec<ToolDefinition>>,
+    options: Option<ChatOptions>,
+    request_id: String,
     channel: Channel<StreamEvent>,
 ) -> Result<ChatResponse, ChatError> {
-    log::info!("Sending chat message to Claude API");
+    let cancel_token = register_cancel_token(&request_id);
+    let result = send_chat_message_inner(
+        message,
+        history,
+        system_prompt,
+        provider,
+        tools,
+        options,
+        cancel_token,
+        &channel,
+    )
+    .await;
+    clear_cancel_token(&request_id);
+    result
+}
 
-    // Get API key from environment
-    let api_key = env::var("ANTHROPIC_API_KEY").map_err(|_| {
-        let error = ChatError::ConfigError {
-            message: "ANTHROPIC_API_KEY not configured".to_string(),
-        };
-        emit_error(&channel, &error);
-        error
-    })?;
+async fn send_chat_message_inner(
+    message: MessageContent,
+    history: Vec<ChatMessage>,
+    system_prompt: Option<String>,
+    provider: Option<Provider>,
+    tools: Option<Vec<ToolDefinition>>,
+    options: Option<ChatOptions>,
+    cancel_token: Arc<AtomicBool>,
+    channel: &Channel<StreamEvent>,
+) -> Result<ChatResponse, ChatError> {
+    let provider = provider.unwrap_or_default();
+    log::info!("Sending chat message via {provider:?} provider");
+
+    let mut backend = make_provider(provider);
 
     // Build message history with new message
     let mut messages = history;
@@ -172,15 +799,27 @@ pub async fn send_chat_message(
         content: message,
     });
 
-    // Build request
-    let request = ClaudeRequest {
-        model: DEFAULT_MODEL.to_string(),
-        max_tokens: DEFAULT_MAX_TOKENS,
+    let options = options.unwrap_or_default();
+    let params = ChatRequestParams {
+        model: options
+            .model
+            .unwrap_or_else(|| default_model_for(provider).to_string()),
+        max_tokens: options.max_tokens.unwrap_or(DEFAULT_MAX_TOKENS),
         messages,
-        stream: true,
-        system: system_prompt,
+        system_prompt,
+        tools,
+        temperature: options.temperature,
+        top_p: options.top_p,
+        stop_sequences: options.stop_sequences,
+        thinking_budget: options.thinking_budget,
     };
 
+    let auth_headers = backend.auth_headers().map_err(|e| {
+        emit_error(channel, &e);
+        e
+    })?;
+    let body = backend.build_request(&params);
+
     // Create HTTP client with timeout
     let client = reqwest::Client::builder()
         .timeout(std::time::Duration::from_secs(CLAUDE_API_TIMEOUT_SECS))
@@ -189,20 +828,25 @@ pub async fn send_chat_message(
             let error = ChatError::NetworkError {
                 message: format!("Failed to create HTTP client: {e}"),
             };
-            emit_error(&channel, &error);
+            emit_error(channel, &error);
             error
         })?;
 
-    // Send request
-    let response = client
-        .post(CLAUDE_API_URL)
-        .header("anthropic-version", ANTHROPIC_VERSION)
-        .header("x-api-key", api_key)
-        .header("content-type", "application/json")
-        .json(&request)
-        .send()
-        .await
-        .map_err(|e| {
+    // Send request, retrying rate limits and server errors with backoff.
+    // Only the pre-stream phase retries: once `stream_response` starts and a
+    // `Token` could have been emitted, resending would duplicate output.
+    let mut attempt: u32 = 0;
+    let response = loop {
+        attempt += 1;
+
+        let mut request = client
+            .post(backend.endpoint())
+            .header("content-type", "application/json");
+        for (name, value) in auth_headers.clone() {
+            request = request.header(name, value);
+        }
+
+        let response = request.json(&body).send().await.map_err(|e| {
             let error = if e.is_timeout() {
                 ChatError::TimeoutError
             } else {
@@ -210,52 +854,98 @@ pub async fn send_chat_message(
                     message: format!("HTTP request failed: {e}"),
                 }
             };
-            emit_error(&channel, &error);
+            emit_error(channel, &error);
             error
         })?;
 
-    // Check status code
-    let status = response.status();
-    if !status.is_success() {
-        let error = match status.as_u16() {
+        let status = response.status();
+        if status.is_success() {
+            break response;
+        }
+
+        let (error, retryable) = match status.as_u16() {
             429 => {
-                // Try to extract retry-after header
                 let retry_after = response
                     .headers()
                     .get("retry-after")
                     .and_then(|v| v.to_str().ok())
                     .and_then(|s| s.parse::<u64>().ok());
-                ChatError::RateLimitError { retry_after }
+                (ChatError::RateLimitError { retry_after }, true)
             }
             500..=599 => {
                 let error_text = response.text().await.unwrap_or_default();
-                ChatError::ApiError {
-                    message: format!("Server error ({}): {error_text}", status.as_u16()),
-                }
+                (
+                    ChatError::ApiError {
+                        message: format!("Server error ({}): {error_text}", status.as_u16()),
+                    },
+                    true,
+                )
             }
             _ => {
                 let error_text = response.text().await.unwrap_or_default();
-                ChatError::ApiError {
-                    message: format!("API error ({}): {error_text}", status.as_u16()),
-                }
+                (
+                    ChatError::ApiError {
+                        message: format!("API error ({}): {error_text}", status.as_u16()),
+                    },
+                    false,
+                )
             }
         };
-        emit_error(&channel, &error);
-        return Err(error);
-    }
+
+        if !retryable || attempt >= MAX_RETRY_ATTEMPTS {
+            emit_error(channel, &error);
+            return Err(error);
+        }
+
+        let delay_secs = match &error {
+            ChatError::RateLimitError {
+                retry_after: Some(secs),
+            } => *secs,
+            _ => backoff_delay_secs(attempt),
+        };
+        log::warn!("Retrying chat request (attempt {attempt}) in {delay_secs}s after {error}");
+        let _ = channel.send(StreamEvent::Retrying {
+            attempt,
+            delay_secs,
+        });
+        // Race the backoff wait against `cancel_token` too, same as
+        // `stream_response`, so a cancel during a (possibly 30s) retry delay
+        // stops the request immediately instead of waiting for the next
+        // attempt's response.
+        tokio::select! {
+            _ = tokio::time::sleep(std::time::Duration::from_secs(delay_secs)) => {}
+            _ = wait_for_cancel(&cancel_token) => {
+                log::debug!("Chat request cancelled during retry backoff");
+                let _ = channel.send(StreamEvent::Cancelled);
+                return Ok(ChatResponse { success: true });
+            }
+        }
+    };
 
     // Parse SSE stream
     log::debug!("Starting SSE stream parsing");
-    stream_response(response, channel).await?;
+    stream_response(response, channel, backend.as_mut(), cancel_token).await?;
 
     log::info!("Chat message completed successfully");
     Ok(ChatResponse { success: true })
 }
 
-/// Streams the SSE response, emitting tokens as they arrive.
+/// Polls `cancel_token` until it's set, at the same low frequency a user
+/// could plausibly notice a "stop generating" click land.
+async fn wait_for_cancel(cancel_token: &AtomicBool) {
+    while !cancel_token.load(Ordering::SeqCst) {
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+    }
+}
+
+/// Streams the SSE response, emitting tokens as they arrive. Races each
+/// chunk against `cancel_token` so a `cancel_chat_message` call can stop
+/// the stream without waiting for the next SSE event (or the timeout).
 async fn stream_response(
     response: reqwest::Response,
-    channel: Channel<StreamEvent>,
+    channel: &Channel<StreamEvent>,
+    provider: &mut dyn ChatProvider,
+    cancel_token: Arc<AtomicBool>,
 ) -> Result<(), ChatError> {
     use eventsource_stream::Eventsource;
     use futures::stream::StreamExt;
@@ -263,14 +953,26 @@ async fn stream_response(
     let mut stream = response.bytes_stream().eventsource();
     let mut sent_done = false;
 
-    while let Some(event) = stream.next().await {
+    loop {
+        let event = tokio::select! {
+            event = stream.next() => event,
+            _ = wait_for_cancel(&cancel_token) => {
+                log::debug!("Stream cancelled by caller");
+                let _ = channel.send(StreamEvent::Cancelled);
+                return Ok(());
+            }
+        };
+
+        let Some(event) = event else {
+            break;
+        };
+
         match event {
             Ok(event) => {
-                // Parse the event data
                 if event.data == "[DONE]" {
                     log::debug!("Stream completed");
                     channel
-                        .send(StreamEvent::Done)
+                        .send(StreamEvent::Done { stop_reason: None })
                         .map_err(|e| ChatError::ParseError {
                             message: format!("Failed to send done event: {e}"),
                         })?;
@@ -278,33 +980,20 @@ async fn stream_response(
                     break;
                 }
 
-                // Parse JSON event
-                match serde_json::from_str::<ClaudeEvent>(&event.data) {
-                    Ok(claude_event) => {
-                        // Extract text from content_block_delta events
-                        if claude_event.event_type == "content_block_delta" {
-                            if let Some(delta) = claude_event.delta {
-                                if delta.delta_type == "text_delta" {
-                                    if let Some(text) = delta.text {
-                                        channel
-                                            .send(StreamEvent::Token { content: text })
-                                            .map_err(|e| ChatError::ParseError {
-                                                message: format!("Failed to send token: {e}"),
-                                            })?;
-                                    }
-                                }
-                            }
-                        } else if claude_event.event_type == "message_stop" {
-                            log::debug!("Message stop event received");
-                            channel
-                                .send(StreamEvent::Done)
-                                .map_err(|e| ChatError::ParseError {
-                                    message: format!("Failed to send done event: {e}"),
-                                })?;
+                match provider.parse_sse_event(&event.data) {
+                    Ok(Some(stream_event)) => {
+                        let is_done = matches!(stream_event, StreamEvent::Done { .. });
+                        channel
+                            .send(stream_event)
+                            .map_err(|e| ChatError::ParseError {
+                                message: format!("Failed to send stream event: {e}"),
+                            })?;
+                        if is_done {
                             sent_done = true;
                             break;
                         }
                     }
+                    Ok(None) => {}
                     Err(e) => {
                         log::warn!("Failed to parse SSE event: {e}, data: {}", event.data);
                         // Continue processing other events instead of failing
@@ -328,7 +1017,7 @@ async fn stream_response(
     // If stream ended without explicit done event, send one now (best-effort)
     if !sent_done {
         log::debug!("Stream ended without explicit done event, sending fallback");
-        let _ = channel.send(StreamEvent::Done);
+        let _ = channel.send(StreamEvent::Done { stop_reason: None });
     }
 
     Ok(())
@@ -346,7 +1035,7 @@ mod tests {
     fn test_chat_message_serialization() {
         let msg = ChatMessage {
             role: "user".to_string(),
-            content: "Hello".to_string(),
+            content: MessageContent::from_text("Hello"),
         };
 
         let json = serde_json::to_string(&msg).unwrap();
@@ -354,6 +1043,66 @@ mod tests {
         assert!(json.contains("Hello"));
     }
 
+    #[test]
+    fn test_message_content_single_text_part_serializes_as_plain_string() {
+        let content = MessageContent::Parts(vec![ContentPart::Text {
+            text: "Hello".to_string(),
+        }]);
+
+        let json = serde_json::to_string(&content).unwrap();
+        assert_eq!(json, r#""Hello""#);
+    }
+
+    #[test]
+    fn test_message_content_with_image_serializes_as_array() {
+        let content = MessageContent::Parts(vec![
+            ContentPart::Text {
+                text: "What's in this image?".to_string(),
+            },
+            ContentPart::Image {
+                source: ImageSource {
+                    source_type: "base64".to_string(),
+                    media_type: "image/png".to_string(),
+                    data: "aGVsbG8=".to_string(),
+                },
+            },
+        ]);
+
+        let json = serde_json::to_value(&content).unwrap();
+        assert!(json.is_array());
+        assert_eq!(json[0]["type"], "text");
+        assert_eq!(json[1]["type"], "image");
+        assert_eq!(json[1]["source"]["media_type"], "image/png");
+    }
+
+    #[test]
+    fn test_message_content_deserializes_plain_string_and_array() {
+        let plain: MessageContent = serde_json::from_str(r#""Hi there""#).unwrap();
+        assert!(matches!(plain, MessageContent::Text(text) if text == "Hi there"));
+
+        let parts: MessageContent =
+            serde_json::from_str(r#"[{"type":"text","text":"Hi"}]"#).unwrap();
+        assert_eq!(parts.as_plain_text(), "Hi");
+    }
+
+    #[test]
+    fn test_message_content_as_plain_text_drops_images() {
+        let content = MessageContent::Parts(vec![
+            ContentPart::Text {
+                text: "caption".to_string(),
+            },
+            ContentPart::Image {
+                source: ImageSource {
+                    source_type: "base64".to_string(),
+                    media_type: "image/jpeg".to_string(),
+                    data: "Zm9v".to_string(),
+                },
+            },
+        ]);
+
+        assert_eq!(content.as_plain_text(), "caption");
+    }
+
     #[test]
     fn test_stream_event_serialization() {
         let event = StreamEvent::Token {
@@ -365,6 +1114,31 @@ mod tests {
         assert!(json.contains("test"));
     }
 
+    #[test]
+    fn test_retrying_event_serialization() {
+        let event = StreamEvent::Retrying {
+            attempt: 2,
+            delay_secs: 4,
+        };
+
+        let json = serde_json::to_string(&event).unwrap();
+        assert!(json.contains("Retrying"));
+        assert!(json.contains("\"attempt\":2"));
+        assert!(json.contains("\"delay_secs\":4"));
+    }
+
+    #[test]
+    fn test_backoff_delay_secs_grows_and_caps() {
+        let first = backoff_delay_secs(1);
+        let second = backoff_delay_secs(2);
+        let third = backoff_delay_secs(3);
+
+        assert!(first >= RETRY_BASE_DELAY_SECS);
+        assert!(second > first);
+        assert!(third > second);
+        assert!(backoff_delay_secs(10) <= RETRY_MAX_DELAY_SECS);
+    }
+
     #[test]
     fn test_chat_error_display() {
         let error = ChatError::ConfigError {
@@ -389,16 +1163,49 @@ mod tests {
             max_tokens: 1024,
             messages: vec![ChatMessage {
                 role: "user".to_string(),
-                content: "Hello".to_string(),
+                content: MessageContent::from_text("Hello"),
             }],
             stream: true,
             system: Some("You are helpful".to_string()),
+            tools: None,
+            temperature: None,
+            top_p: None,
+            stop_sequences: None,
+            thinking: None,
         };
 
         let json = serde_json::to_string(&request).unwrap();
         assert!(json.contains("claude-sonnet-4-5"));
         assert!(json.contains("Hello"));
         assert!(json.contains("helpful"));
+        assert!(!json.contains("tools"));
+        assert!(!json.contains("temperature"));
+        assert!(!json.contains("thinking"));
+    }
+
+    #[test]
+    fn test_claude_request_includes_options_when_present() {
+        let request = ClaudeRequest {
+            model: "claude-sonnet-4-5-20250929".to_string(),
+            max_tokens: 1024,
+            messages: vec![],
+            stream: true,
+            system: None,
+            tools: None,
+            temperature: Some(0.7),
+            top_p: Some(0.9),
+            stop_sequences: Some(vec!["STOP".to_string()]),
+            thinking: Some(ClaudeThinking {
+                thinking_type: "enabled",
+                budget_tokens: 2048,
+            }),
+        };
+
+        let json = serde_json::to_string(&request).unwrap();
+        assert!(json.contains("\"temperature\":0.7"));
+        assert!(json.contains("\"top_p\":0.9"));
+        assert!(json.contains("STOP"));
+        assert!(json.contains("\"budget_tokens\":2048"));
     }
 
     #[test]
@@ -417,7 +1224,213 @@ mod tests {
         assert!(event.delta.is_none());
     }
 
-    // Note: Integration tests with real API require ANTHROPIC_API_KEY
+    #[test]
+    fn test_claude_provider_parses_text_delta() {
+        let mut provider = ClaudeProvider::default();
+        let json = r#"{"type":"content_block_delta","delta":{"type":"text_delta","text":"Hi"}}"#;
+        let event = provider.parse_sse_event(json).unwrap();
+        assert!(matches!(event, Some(StreamEvent::Token { content }) if content == "Hi"));
+    }
+
+    #[test]
+    fn test_claude_provider_parses_message_stop() {
+        let mut provider = ClaudeProvider::default();
+        let event = provider.parse_sse_event(r#"{"type":"message_stop"}"#).unwrap();
+        assert!(matches!(event, Some(StreamEvent::Done { stop_reason: None })));
+    }
+
+    #[test]
+    fn test_claude_provider_reports_usage_and_stop_reason() {
+        let mut provider = ClaudeProvider::default();
+
+        let start = provider
+            .parse_sse_event(r#"{"type":"message_start","message":{"usage":{"input_tokens":42}}}"#)
+            .unwrap();
+        assert!(start.is_none());
+
+        let usage = provider
+            .parse_sse_event(
+                r#"{"type":"message_delta","delta":{"stop_reason":"end_turn"},"usage":{"output_tokens":17}}"#,
+            )
+            .unwrap();
+        match usage {
+            Some(StreamEvent::Usage { input_tokens, output_tokens }) => {
+                assert_eq!(input_tokens, 42);
+                assert_eq!(output_tokens, 17);
+            }
+            other => panic!("expected Usage, got {other:?}"),
+        }
+
+        let stop = provider.parse_sse_event(r#"{"type":"message_stop"}"#).unwrap();
+        match stop {
+            Some(StreamEvent::Done { stop_reason }) => {
+                assert_eq!(stop_reason.as_deref(), Some("end_turn"));
+            }
+            other => panic!("expected Done, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_claude_provider_max_tokens_stop_reason_survives_to_done() {
+        let mut provider = ClaudeProvider::default();
+        provider
+            .parse_sse_event(r#"{"type":"message_delta","delta":{"stop_reason":"max_tokens"},"usage":{"output_tokens":4096}}"#)
+            .unwrap();
+        let stop = provider.parse_sse_event(r#"{"type":"message_stop"}"#).unwrap();
+        assert!(matches!(
+            stop,
+            Some(StreamEvent::Done { stop_reason }) if stop_reason.as_deref() == Some("max_tokens")
+        ));
+    }
+
+    #[test]
+    fn test_claude_provider_streams_tool_use() {
+        let mut provider = ClaudeProvider::default();
+
+        let start = provider
+            .parse_sse_event(
+                r#"{"type":"content_block_start","index":0,"content_block":{"type":"tool_use","id":"toolu_1","name":"get_weather"}}"#,
+            )
+            .unwrap();
+        assert!(
+            matches!(start, Some(StreamEvent::ToolUseStart { id, name }) if id == "toolu_1" && name == "get_weather")
+        );
+
+        let delta1 = provider
+            .parse_sse_event(
+                r#"{"type":"content_block_delta","index":0,"delta":{"type":"input_json_delta","partial_json":"{\"loc"}}"#,
+            )
+            .unwrap();
+        assert!(delta1.is_none());
+
+        let delta2 = provider
+            .parse_sse_event(
+                r#"{"type":"content_block_delta","index":0,"delta":{"type":"input_json_delta","partial_json":"ation\":\"NYC\"}"}}"#,
+            )
+            .unwrap();
+        assert!(delta2.is_none());
+
+        let stop = provider
+            .parse_sse_event(r#"{"type":"content_block_stop","index":0}"#)
+            .unwrap();
+        match stop {
+            Some(StreamEvent::ToolUseInput { id, input }) => {
+                assert_eq!(id, "toolu_1");
+                assert_eq!(input["location"], "NYC");
+            }
+            other => panic!("expected ToolUseInput, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_claude_provider_ignores_text_block_stop() {
+        let mut provider = ClaudeProvider::default();
+        provider
+            .parse_sse_event(
+                r#"{"type":"content_block_start","index":0,"content_block":{"type":"text"}}"#,
+            )
+            .unwrap();
+        let event = provider
+            .parse_sse_event(r#"{"type":"content_block_stop","index":0}"#)
+            .unwrap();
+        assert!(event.is_none());
+    }
+
+    #[test]
+    fn test_openai_compatible_request_includes_system_message() {
+        let params = ChatRequestParams {
+            model: "gpt-4o-mini".to_string(),
+            max_tokens: 512,
+            messages: vec![ChatMessage {
+                role: "user".to_string(),
+                content: MessageContent::from_text("Hi"),
+            }],
+            system_prompt: Some("Be terse".to_string()),
+            tools: None,
+            temperature: None,
+            top_p: None,
+            stop_sequences: None,
+            thinking_budget: None,
+        };
+
+        let body = build_openai_compatible_request(&params);
+        let messages = body["messages"].as_array().unwrap();
+        assert_eq!(messages[0]["role"], "system");
+        assert_eq!(messages[0]["content"], "Be terse");
+        assert_eq!(messages[1]["role"], "user");
+        assert!(body.get("temperature").is_none());
+    }
+
+    #[test]
+    fn test_openai_compatible_request_includes_sampling_options() {
+        let params = ChatRequestParams {
+            model: "gpt-4o-mini".to_string(),
+            max_tokens: 512,
+            messages: vec![],
+            system_prompt: None,
+            tools: None,
+            temperature: Some(0.5),
+            top_p: Some(0.8),
+            stop_sequences: Some(vec!["END".to_string()]),
+            thinking_budget: None,
+        };
+
+        let body = build_openai_compatible_request(&params);
+        assert_eq!(body["temperature"], 0.5);
+        assert_eq!(body["top_p"], 0.8);
+        assert_eq!(body["stop"][0], "END");
+    }
+
+    #[test]
+    fn test_parse_openai_compatible_event_token() {
+        let json = r#"{"choices":[{"delta":{"content":"Hi"},"finish_reason":null}]}"#;
+        let event = parse_openai_compatible_event(json).unwrap();
+        assert!(matches!(event, Some(StreamEvent::Token { content }) if content == "Hi"));
+    }
+
+    #[test]
+    fn test_parse_openai_compatible_event_finish_reason_is_done() {
+        let json = r#"{"choices":[{"delta":{},"finish_reason":"stop"}]}"#;
+        let event = parse_openai_compatible_event(json).unwrap();
+        assert!(matches!(
+            event,
+            Some(StreamEvent::Done { stop_reason }) if stop_reason.as_deref() == Some("stop")
+        ));
+    }
+
+    #[test]
+    fn test_default_model_for_each_provider() {
+        assert_eq!(default_model_for(Provider::Claude), DEFAULT_MODEL);
+        assert_eq!(default_model_for(Provider::OpenAi), DEFAULT_OPENAI_MODEL);
+        assert_eq!(default_model_for(Provider::Ollama), DEFAULT_OLLAMA_MODEL);
+    }
+
+    #[test]
+    fn test_cancel_chat_message_flags_registered_token() {
+        let request_id = "test-cancel-flags-registered-token";
+        let token = register_cancel_token(request_id);
+        assert!(!token.load(Ordering::SeqCst));
+
+        assert!(cancel_chat_message(request_id.to_string()));
+        assert!(token.load(Ordering::SeqCst));
+
+        clear_cancel_token(request_id);
+    }
+
+    #[test]
+    fn test_cancel_chat_message_unknown_request_id_returns_false() {
+        assert!(!cancel_chat_message("no-such-request".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_cancel_resolves_once_flagged() {
+        let flag = AtomicBool::new(false);
+        flag.store(true, Ordering::SeqCst);
+        // Should resolve immediately since the flag is already set.
+        wait_for_cancel(&flag).await;
+    }
+
+    // Note: Integration tests with real APIs require provider credentials.
     // These are intentionally left as unit tests only.
     // Manual testing required for full API integration.
 }
@@ -1,16 +1,28 @@
 //! Context file upload and parsing commands.
 //!
-//! Handles uploading and parsing CSV, PDF, and Excel files to project context directory.
+//! Handles uploading and parsing CSV, PDF, Excel, Markdown, and source-code
+//! files to project context directory.
 //! Uses spawn_blocking for heavy I/O operations and Tauri channels for progress updates.
 
-use crate::types::{ContextFileRecord, UploadProgress};
+use crate::commands::git::git_auto_commit;
+use crate::commands::lfs::{ensure_lfs_gitattributes_entry, store_lfs_object};
+use crate::commands::markdown::render_markdown_to_html;
+use crate::commands::projects::detect_git_lfs;
+use crate::types::{ContextFileRecord, FileIntegrity, TypeOfFile, UploadProgress};
 use calamine::{open_workbook_auto, Reader};
 use csv::ReaderBuilder;
-use git2::Repository;
 use lopdf::Document;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
 use std::fs;
+use std::io::{BufReader, Read};
 use std::panic::{catch_unwind, AssertUnwindSafe};
 use std::path::{Path, PathBuf};
+use std::sync::{LazyLock, Mutex};
+use std::time::{Duration, Instant};
+use syntect::html::{ClassStyle, ClassedHTMLGenerator};
+use syntect::parsing::{SyntaxReference, SyntaxSet};
+use syntect::util::LinesWithEndings;
 use tauri::ipc::Channel;
 
 /// Maximum size for preview text (500 characters as per spec)
@@ -19,6 +31,385 @@ const MAX_PREVIEW_CHARS: usize = 500;
 /// Large File Storage threshold (10MB)
 const LFS_THRESHOLD_BYTES: u64 = 10_485_760;
 
+/// Extensions treated as Markdown/plain-text/source-code context files,
+/// routed through [`parse_text`] rather than the CSV/PDF/Excel parsers.
+const TEXT_EXTENSIONS: &[&str] = &[
+    "md", "markdown", "txt", "rs", "py", "js", "jsx", "ts", "tsx", "go", "java", "c", "h", "cpp",
+    "hpp", "cs", "rb", "php", "sh", "bash", "zsh", "sql", "json", "yaml", "yml", "toml", "html",
+    "htm", "css", "scss", "swift", "kt", "kts", "scala", "pl", "lua", "r", "dart", "ex", "exs",
+    "erl", "clj", "hs", "ml", "jl",
+];
+
+/// Loaded once and shared for the lifetime of the process; building a
+/// `SyntaxSet` from the bundled definitions is relatively expensive. Mirrors
+/// `markdown.rs`'s `SYNTAX_SET`, kept separate since each module renders
+/// previews for a different surface (full documents vs. upload previews).
+static SYNTAX_SET: LazyLock<SyntaxSet> = LazyLock::new(SyntaxSet::load_defaults_newlines);
+
+/// How long a rendered text/markdown preview remains cached before being
+/// re-rendered.
+const PREVIEW_CACHE_TTL: Duration = Duration::from_secs(300);
+
+/// Maximum number of rendered previews held in [`PREVIEW_CACHE`] at once;
+/// the oldest entry is evicted to make room past this.
+const PREVIEW_CACHE_CAPACITY: usize = 100;
+
+struct PreviewCacheEntry {
+    cached_at: Instant,
+    file_type: String,
+    html_preview: Option<String>,
+    text_preview: Option<String>,
+}
+
+/// Process-lifetime cache of rendered Markdown/source-code previews, keyed
+/// by `(stored_filename, content_hash)` so re-uploading the same file under
+/// the same name doesn't re-run Markdown rendering or syntax highlighting.
+/// Mirrors the TTL-based `STATUS_CACHE` in `git.rs`, with a capacity bound
+/// added since this cache is keyed per-file rather than per-project.
+static PREVIEW_CACHE: LazyLock<Mutex<HashMap<(String, String), PreviewCacheEntry>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Guards read-modify-write access to `context/.manifest.json` so
+/// concurrent uploads don't race each other's dedup check and lose an entry.
+static MANIFEST_LOCK: LazyLock<Mutex<()>> = LazyLock::new(|| Mutex::new(()));
+
+/// Stream `path`'s contents through SHA-256 to compute a content-addressing
+/// digest, without loading the whole file into memory.
+fn hash_file_contents(path: &Path) -> Result<String, String> {
+    let file = fs::File::open(path).map_err(|e| format!("Failed to open file for hashing: {e}"))?;
+    let mut reader = BufReader::new(file);
+    let mut hasher = Sha256::new();
+    let mut buf = [0u8; 65536];
+
+    loop {
+        let bytes_read = reader
+            .read(&mut buf)
+            .map_err(|e| format!("Failed to read file for hashing: {e}"))?;
+        if bytes_read == 0 {
+            break;
+        }
+        hasher.update(&buf[..bytes_read]);
+    }
+
+    Ok(hasher.finalize().iter().map(|byte| format!("{byte:02x}")).collect())
+}
+
+/// Load `context/.manifest.json`, the digest -> record map used to detect
+/// re-uploads of the same file under a different name. Returns an empty
+/// manifest if the file doesn't exist yet.
+fn load_context_manifest(context_dir: &Path) -> Result<HashMap<String, ContextFileRecord>, String> {
+    let manifest_path = context_dir.join(".manifest.json");
+    if !manifest_path.exists() {
+        return Ok(HashMap::new());
+    }
+
+    let content = fs::read_to_string(&manifest_path)
+        .map_err(|e| format!("Failed to read context manifest: {e}"))?;
+    serde_json::from_str(&content).map_err(|e| format!("Failed to parse context manifest: {e}"))
+}
+
+/// Persist `manifest` to `context/.manifest.json`.
+fn save_context_manifest(
+    context_dir: &Path,
+    manifest: &HashMap<String, ContextFileRecord>,
+) -> Result<(), String> {
+    let manifest_path = context_dir.join(".manifest.json");
+    let content = serde_json::to_string_pretty(manifest)
+        .map_err(|e| format!("Failed to serialize context manifest: {e}"))?;
+    fs::write(&manifest_path, content).map_err(|e| format!("Failed to write context manifest: {e}"))
+}
+
+/// Resolve a unique filename under `context_dir` by appending -2, -3, etc.
+/// if `filename` already exists (e.g. different content reusing the same
+/// slugified name).
+fn resolve_unique_filename(context_dir: &Path, filename: &str) -> String {
+    if !context_dir.join(filename).exists() {
+        return filename.to_string();
+    }
+
+    let path = Path::new(filename);
+    let stem = path.file_stem().unwrap_or_default().to_string_lossy().to_string();
+    let ext = path.extension().map(|ext| ext.to_string_lossy().to_string());
+
+    let mut counter = 2;
+    loop {
+        let candidate = match &ext {
+            Some(ext) => format!("{stem}-{counter}.{ext}"),
+            None => format!("{stem}-{counter}"),
+        };
+        if !context_dir.join(&candidate).exists() {
+            return candidate;
+        }
+        counter += 1;
+    }
+}
+
+/// Sniff `path`'s actual file type from its magic bytes, independent of
+/// its extension. Returns [`TypeOfFile::Unknown`] when the bytes carry no
+/// recognizable signature (as with plain-text CSV, which has none).
+fn sniff_file_type(path: &Path) -> Result<TypeOfFile, String> {
+    let mut file =
+        fs::File::open(path).map_err(|e| format!("Failed to open file for type detection: {e}"))?;
+    let mut header = [0u8; 8];
+    let bytes_read = file
+        .read(&mut header)
+        .map_err(|e| format!("Failed to read file header: {e}"))?;
+    let header = &header[..bytes_read];
+
+    if header.starts_with(b"%PDF-") {
+        return Ok(TypeOfFile::Pdf);
+    }
+
+    // Legacy OLE-based .xls
+    if header.starts_with(&[0xD0, 0xCF, 0x11, 0xE0, 0xA1, 0xB1, 0x1A, 0xE1]) {
+        return Ok(TypeOfFile::Excel);
+    }
+
+    if header.starts_with(&[0x89, b'P', b'N', b'G'])
+        || header.starts_with(&[0xFF, 0xD8, 0xFF])
+        || header.starts_with(b"GIF8")
+    {
+        return Ok(TypeOfFile::Image);
+    }
+
+    if header.starts_with(b"PK\x03\x04") || header.starts_with(b"PK\x05\x06") {
+        return Ok(if zip_entry_names_suggest_excel(path) {
+            TypeOfFile::Excel
+        } else {
+            TypeOfFile::Zip
+        });
+    }
+
+    Ok(TypeOfFile::Unknown)
+}
+
+/// Best-effort check for whether a ZIP-backed file's entry names look like
+/// an xlsx workbook (e.g. `xl/workbook.xml`). ZIP local file headers store
+/// entry names as plaintext ahead of each entry's (possibly compressed)
+/// data, so this doesn't require decompressing anything — just scanning
+/// the leading bytes where those headers live.
+fn zip_entry_names_suggest_excel(path: &Path) -> bool {
+    const PROBE_BYTES: usize = 8192;
+
+    let Ok(mut file) = fs::File::open(path) else {
+        return false;
+    };
+    let mut buf = vec![0u8; PROBE_BYTES];
+    let Ok(bytes_read) = file.read(&mut buf) else {
+        return false;
+    };
+
+    buf[..bytes_read].windows(3).any(|window| window == b"xl/")
+}
+
+/// Classify a file's actual type via magic-byte sniffing, falling back to
+/// `extension_hint` only when the bytes carry no recognizable signature
+/// (as with CSV) — so a mislabeled `.csv` that's really a PDF is still caught.
+fn classify_file_type(path: &Path, extension_hint: &str) -> Result<TypeOfFile, String> {
+    Ok(match sniff_file_type(path)? {
+        TypeOfFile::Unknown => match extension_hint {
+            "csv" => TypeOfFile::Csv,
+            "pdf" => TypeOfFile::Pdf,
+            "xlsx" | "xls" => TypeOfFile::Excel,
+            ext if TEXT_EXTENSIONS.contains(&ext) => TypeOfFile::Text,
+            _ => TypeOfFile::Unknown,
+        },
+        sniffed => sniffed,
+    })
+}
+
+/// Scan for the ZIP end-of-central-directory record, confirming the
+/// archive isn't truncated or otherwise structurally broken.
+fn has_zip_end_of_central_directory(bytes: &[u8]) -> bool {
+    const EOCD_SIGNATURE: [u8; 4] = [0x50, 0x4B, 0x05, 0x06];
+    const MAX_COMMENT_LEN: usize = 65535;
+
+    let search_start = bytes.len().saturating_sub(22 + MAX_COMMENT_LEN);
+    bytes[search_start..]
+        .windows(EOCD_SIGNATURE.len())
+        .any(|window| window == EOCD_SIGNATURE)
+}
+
+/// Number of leading lines sampled when scoring candidate delimiters.
+const DELIMITER_SAMPLE_LINES: usize = 20;
+
+/// Decode `bytes` into text, honoring a UTF-8/UTF-16 BOM if present, and
+/// falling back to Windows-1252 when the bytes aren't valid UTF-8 (as with
+/// many European CSV exports or legacy source files). Returns the decoded
+/// text and the encoding label. Shared by the CSV and plain-text/Markdown
+/// parsers.
+fn decode_text_bytes(bytes: &[u8]) -> (String, &'static str) {
+    if let Some(rest) = bytes.strip_prefix(&[0xEF, 0xBB, 0xBF]) {
+        return (String::from_utf8_lossy(rest).to_string(), "utf-8");
+    }
+
+    if let Some(rest) = bytes.strip_prefix(&[0xFF, 0xFE]) {
+        let units: Vec<u16> = rest
+            .chunks_exact(2)
+            .map(|c| u16::from_le_bytes([c[0], c[1]]))
+            .collect();
+        return (String::from_utf16_lossy(&units), "utf-16");
+    }
+
+    if let Some(rest) = bytes.strip_prefix(&[0xFE, 0xFF]) {
+        let units: Vec<u16> = rest
+            .chunks_exact(2)
+            .map(|c| u16::from_be_bytes([c[0], c[1]]))
+            .collect();
+        return (String::from_utf16_lossy(&units), "utf-16");
+    }
+
+    match std::str::from_utf8(bytes) {
+        Ok(s) => (s.to_string(), "utf-8"),
+        Err(_) => (decode_windows_1252(bytes), "windows-1252"),
+    }
+}
+
+/// Decode `bytes` as Windows-1252. Identical to Latin-1 outside the
+/// `0x80..=0x9F` range, which Windows-1252 maps to printable characters
+/// (smart quotes, em dash, etc.) instead of C1 control codes.
+fn decode_windows_1252(bytes: &[u8]) -> String {
+    const C1_TABLE: [char; 32] = [
+        '\u{20AC}', '\u{81}', '\u{201A}', '\u{192}', '\u{201E}', '\u{2026}', '\u{2020}',
+        '\u{2021}', '\u{2C6}', '\u{2030}', '\u{160}', '\u{2039}', '\u{152}', '\u{8D}', '\u{17D}',
+        '\u{8F}', '\u{90}', '\u{2018}', '\u{2019}', '\u{201C}', '\u{201D}', '\u{2022}', '\u{2013}',
+        '\u{2014}', '\u{2DC}', '\u{2122}', '\u{161}', '\u{203A}', '\u{153}', '\u{9D}', '\u{17E}',
+        '\u{178}',
+    ];
+
+    bytes
+        .iter()
+        .map(|&b| match b {
+            0x80..=0x9F => C1_TABLE[(b - 0x80) as usize],
+            other => other as char,
+        })
+        .collect()
+}
+
+/// Sniff the CSV delimiter among `,`, `;`, tab, and `|` by tokenizing the
+/// first [`DELIMITER_SAMPLE_LINES`] lines with each candidate and picking
+/// the one whose per-row column count is both >1 and most consistent
+/// (lowest variance). Falls back to `,` when no candidate yields >1 column.
+fn detect_csv_delimiter(sample: &str) -> u8 {
+    const CANDIDATES: [u8; 4] = [b',', b';', b'\t', b'|'];
+
+    let lines: Vec<&str> = sample
+        .lines()
+        .filter(|line| !line.is_empty())
+        .take(DELIMITER_SAMPLE_LINES)
+        .collect();
+    if lines.is_empty() {
+        return b',';
+    }
+
+    let mut best: Option<(u8, f64)> = None;
+    for &delimiter in &CANDIDATES {
+        let counts: Vec<usize> = lines
+            .iter()
+            .map(|line| line.matches(delimiter as char).count() + 1)
+            .collect();
+
+        let mean = counts.iter().sum::<usize>() as f64 / counts.len() as f64;
+        if mean <= 1.0 {
+            continue;
+        }
+
+        let sum_sq_diff: f64 = counts.iter().map(|&c| (c as f64 - mean).powi(2)).sum();
+        let variance = sum_sq_diff / counts.len() as f64;
+
+        if best.map_or(true, |(_, best_variance)| variance < best_variance) {
+            best = Some((delimiter, variance));
+        }
+    }
+
+    best.map(|(delimiter, _)| delimiter).unwrap_or(b',')
+}
+
+/// Detect ragged or unparseable rows by re-reading the already-decoded CSV
+/// text strictly (non-flexible) with the detected `delimiter`, which errors
+/// on any row whose field count doesn't match the header.
+fn validate_csv_integrity(content: &str, delimiter: u8) -> FileIntegrity {
+    let mut reader = ReaderBuilder::new()
+        .flexible(false)
+        .delimiter(delimiter)
+        .from_reader(content.as_bytes());
+
+    for result in reader.records() {
+        if let Err(e) = result {
+            return FileIntegrity {
+                ok: false,
+                error_string: format!("CSV has ragged or unparseable rows: {e}"),
+            };
+        }
+    }
+
+    FileIntegrity {
+        ok: true,
+        error_string: String::new(),
+    }
+}
+
+/// Verify the ZIP central-directory end record, and for Excel files, that
+/// the required sheet XML parts actually open. Legacy OLE-based `.xls`
+/// files aren't zip-backed at all, so the EOCD check only applies when the
+/// bytes actually start with a ZIP signature.
+fn validate_zip_integrity(path: &Path, is_excel: bool) -> FileIntegrity {
+    let bytes = match fs::read(path) {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            return FileIntegrity {
+                ok: false,
+                error_string: format!("Failed to read file: {e}"),
+            }
+        }
+    };
+
+    if bytes.starts_with(b"PK") && !has_zip_end_of_central_directory(&bytes) {
+        return FileIntegrity {
+            ok: false,
+            error_string: "ZIP end-of-central-directory record not found (truncated or corrupted \
+                archive)"
+                .to_string(),
+        };
+    }
+
+    if !is_excel {
+        return FileIntegrity {
+            ok: true,
+            error_string: String::new(),
+        };
+    }
+
+    let mut workbook = match open_workbook_auto(path) {
+        Ok(workbook) => workbook,
+        Err(e) => {
+            return FileIntegrity {
+                ok: false,
+                error_string: format!("Failed to open Excel file: {e}"),
+            }
+        }
+    };
+
+    let sheet_names = workbook.sheet_names().to_vec();
+    let Some(first_sheet) = sheet_names.first() else {
+        return FileIntegrity {
+            ok: false,
+            error_string: "Excel file has no sheets".to_string(),
+        };
+    };
+
+    match workbook.worksheet_range(first_sheet) {
+        Ok(_) => FileIntegrity {
+            ok: true,
+            error_string: String::new(),
+        },
+        Err(e) => FileIntegrity {
+            ok: false,
+            error_string: format!("Failed to open Excel sheet data: {e}"),
+        },
+    }
+}
+
 /// Sanitize a filename for safe filesystem storage.
 /// Converts to lowercase, replaces spaces/special chars with hyphens.
 fn sanitize_filename(filename: &str) -> String {
@@ -51,10 +442,14 @@ fn sanitize_filename(filename: &str) -> String {
 fn parse_csv(path: &Path) -> Result<ContextFileRecord, String> {
     log::debug!("Parsing CSV file: {path:?}");
 
-    let content = fs::read_to_string(path).map_err(|e| format!("Failed to read CSV file: {e}"))?;
+    let raw_bytes = fs::read(path).map_err(|e| format!("Failed to read CSV file: {e}"))?;
+    let (content, encoding) = decode_text_bytes(&raw_bytes);
+    let delimiter = detect_csv_delimiter(&content);
+    let delimiter_str = (delimiter as char).to_string();
 
     let mut reader = ReaderBuilder::new()
         .flexible(true) // Handle variable column counts
+        .delimiter(delimiter)
         .from_reader(content.as_bytes());
 
     // Extract headers
@@ -70,18 +465,19 @@ fn parse_csv(path: &Path) -> Result<ContextFileRecord, String> {
     // Generate preview (first 10 rows)
     let mut preview_reader = ReaderBuilder::new()
         .flexible(true)
+        .delimiter(delimiter)
         .from_reader(content.as_bytes());
 
     let mut preview_lines = Vec::new();
 
     // Add header
     if let Ok(headers) = preview_reader.headers() {
-        preview_lines.push(headers.iter().collect::<Vec<_>>().join(","));
+        preview_lines.push(headers.iter().collect::<Vec<_>>().join(&delimiter_str));
     }
 
     // Add up to 10 data rows
     for record in preview_reader.records().take(10).flatten() {
-        preview_lines.push(record.iter().collect::<Vec<_>>().join(","));
+        preview_lines.push(record.iter().collect::<Vec<_>>().join(&delimiter_str));
     }
 
     let preview = preview_lines.join("\n");
@@ -117,6 +513,12 @@ fn parse_csv(path: &Path) -> Result<ContextFileRecord, String> {
         size_bytes: metadata.len(),
         relative_file_path: format!("context/{stored_filename}"),
         is_lfs: metadata.len() > LFS_THRESHOLD_BYTES,
+        content_hash: hash_file_contents(path)?,
+        sniffed_type: TypeOfFile::Csv,
+        integrity: validate_csv_integrity(&content, delimiter),
+        delimiter: Some(delimiter_str),
+        encoding: Some(encoding.to_string()),
+        html_preview: None,
     })
 }
 
@@ -159,7 +561,7 @@ fn parse_pdf(path: &Path) -> Result<ContextFileRecord, String> {
     // Wrap PDF parsing in catch_unwind for stability
     let parse_result = catch_unwind(AssertUnwindSafe(|| Document::load(path)));
 
-    let (pages, text_preview) = match parse_result {
+    let (pages, text_preview, integrity) = match parse_result {
         Ok(Ok(doc)) => {
             let page_count = doc.get_pages().len() as u32;
 
@@ -173,15 +575,47 @@ fn parse_pdf(path: &Path) -> Result<ContextFileRecord, String> {
                 text
             };
 
-            (Some(page_count), Some(preview))
+            // At least one page object should actually resolve
+            let integrity = match doc.get_pages().into_iter().next() {
+                Some((_, page_id)) => match doc.get_object(page_id) {
+                    Ok(_) => FileIntegrity {
+                        ok: true,
+                        error_string: String::new(),
+                    },
+                    Err(e) => FileIntegrity {
+                        ok: false,
+                        error_string: format!("PDF page object did not resolve: {e}"),
+                    },
+                },
+                None => FileIntegrity {
+                    ok: false,
+                    error_string: "PDF has no pages".to_string(),
+                },
+            };
+
+            (Some(page_count), Some(preview), integrity)
         }
         Ok(Err(e)) => {
             log::warn!("Failed to parse PDF: {e}");
-            (None, Some("(Failed to parse PDF)".to_string()))
+            (
+                None,
+                Some("(Failed to parse PDF)".to_string()),
+                FileIntegrity {
+                    ok: false,
+                    error_string: format!("Failed to parse PDF: {e}"),
+                },
+            )
         }
         Err(_) => {
             log::warn!("PDF parsing panicked, likely corrupted file");
-            (None, Some("(Corrupted PDF)".to_string()))
+            (
+                None,
+                Some("(Corrupted PDF)".to_string()),
+                FileIntegrity {
+                    ok: false,
+                    error_string: "PDF parsing panicked, likely corrupted file".to_string(),
+                },
+            )
         }
     };
 
@@ -198,6 +632,12 @@ fn parse_pdf(path: &Path) -> Result<ContextFileRecord, String> {
         size_bytes: metadata.len(),
         relative_file_path: format!("context/{stored_filename}"),
         is_lfs: metadata.len() > LFS_THRESHOLD_BYTES,
+        content_hash: hash_file_contents(path)?,
+        sniffed_type: TypeOfFile::Pdf,
+        integrity,
+        delimiter: None,
+        encoding: None,
+        html_preview: None,
     })
 }
 
@@ -285,6 +725,8 @@ fn parse_excel(path: &Path) -> Result<ContextFileRecord, String> {
         None
     };
 
+    let integrity = validate_zip_integrity(path, true);
+
     Ok(ContextFileRecord {
         original_filename: filename,
         stored_filename: stored_filename.clone(),
@@ -298,13 +740,151 @@ fn parse_excel(path: &Path) -> Result<ContextFileRecord, String> {
         size_bytes: metadata.len(),
         relative_file_path: format!("context/{stored_filename}"),
         is_lfs: metadata.len() > LFS_THRESHOLD_BYTES,
+        content_hash: hash_file_contents(path)?,
+        sniffed_type: TypeOfFile::Excel,
+        integrity,
+        delimiter: None,
+        encoding: None,
+        html_preview: None,
+    })
+}
+
+/// Render (or reuse a cached render of) the preview for a Markdown,
+/// plain-text, or source-code file: Markdown renders to sanitized HTML,
+/// recognized source extensions get syntax-highlighted HTML, and anything
+/// else falls back to a plain-text excerpt. Returns `(file_type,
+/// html_preview, text_preview)`.
+fn render_text_preview(
+    stored_filename: &str,
+    content_hash: &str,
+    content: &str,
+    extension: &str,
+) -> (String, Option<String>, Option<String>) {
+    let cache_key = (stored_filename.to_string(), content_hash.to_string());
+
+    if let Some(entry) = PREVIEW_CACHE.lock().unwrap().get(&cache_key) {
+        if entry.cached_at.elapsed() < PREVIEW_CACHE_TTL {
+            return (
+                entry.file_type.clone(),
+                entry.html_preview.clone(),
+                entry.text_preview.clone(),
+            );
+        }
+    }
+
+    let excerpt: String = content.chars().take(MAX_PREVIEW_CHARS).collect();
+
+    let (file_type, html_preview, text_preview) = if matches!(extension, "md" | "markdown") {
+        (
+            "markdown".to_string(),
+            Some(render_markdown_to_html(&excerpt)),
+            None,
+        )
+    } else if let Some(syntax) = SYNTAX_SET
+        .find_syntax_by_extension(extension)
+        .filter(|syntax| syntax.name != "Plain Text")
+    {
+        (
+            syntax.name.to_lowercase(),
+            Some(highlight_source_excerpt(&excerpt, syntax)),
+            None,
+        )
+    } else {
+        ("text".to_string(), None, Some(excerpt))
+    };
+
+    let mut cache = PREVIEW_CACHE.lock().unwrap();
+    if !cache.contains_key(&cache_key) && cache.len() >= PREVIEW_CACHE_CAPACITY {
+        if let Some(oldest_key) = cache
+            .iter()
+            .min_by_key(|(_, entry)| entry.cached_at)
+            .map(|(key, _)| key.clone())
+        {
+            cache.remove(&oldest_key);
+        }
+    }
+    cache.insert(
+        cache_key,
+        PreviewCacheEntry {
+            cached_at: Instant::now(),
+            file_type: file_type.clone(),
+            html_preview: html_preview.clone(),
+            text_preview: text_preview.clone(),
+        },
+    );
+    drop(cache);
+
+    (file_type, html_preview, text_preview)
+}
+
+/// Syntax-highlight `excerpt` against `syntax`, emitting `<span>`s with CSS
+/// classes (via `ClassedHTMLGenerator`) so highlighting follows the active
+/// light/dark/system theme, mirroring `markdown.rs`'s `highlight_code_block`.
+fn highlight_source_excerpt(excerpt: &str, syntax: &SyntaxReference) -> String {
+    let mut generator =
+        ClassedHTMLGenerator::new_with_class_style(syntax, &SYNTAX_SET, ClassStyle::Spaced);
+
+    for line in LinesWithEndings::from(excerpt) {
+        // Best-effort: a highlighting failure shouldn't fail the whole render.
+        let _ = generator.parse_html_for_line_which_includes_newline(line);
+    }
+
+    format!(
+        "<pre class=\"highlight\"><code>{}</code></pre>",
+        generator.finalize()
+    )
+}
+
+/// Parse a Markdown, plain-text, or source-code context file.
+fn parse_text(path: &Path, extension: &str) -> Result<ContextFileRecord, String> {
+    log::debug!("Parsing text file: {path:?}");
+
+    let raw_bytes = fs::read(path).map_err(|e| format!("Failed to read text file: {e}"))?;
+    let (content, _encoding) = decode_text_bytes(&raw_bytes);
+
+    let metadata = fs::metadata(path).map_err(|e| format!("Failed to read file metadata: {e}"))?;
+    let filename = path
+        .file_name()
+        .unwrap_or_default()
+        .to_string_lossy()
+        .to_string();
+    let stored_filename = sanitize_filename(&filename);
+    let content_hash = hash_file_contents(path)?;
+
+    let (file_type, html_preview, text_preview) =
+        render_text_preview(&stored_filename, &content_hash, &content, extension);
+
+    Ok(ContextFileRecord {
+        original_filename: filename,
+        stored_filename: stored_filename.clone(),
+        file_type,
+        detected_type: None,
+        rows: None,
+        columns: None,
+        preview: None,
+        pages: None,
+        text_preview,
+        size_bytes: metadata.len(),
+        relative_file_path: format!("context/{stored_filename}"),
+        is_lfs: metadata.len() > LFS_THRESHOLD_BYTES,
+        content_hash,
+        sniffed_type: TypeOfFile::Text,
+        integrity: FileIntegrity {
+            ok: true,
+            error_string: String::new(),
+        },
+        delimiter: None,
+        encoding: None,
+        html_preview,
     })
 }
 
 /// Upload a context file to the project.
 ///
-/// Parses the file (CSV/PDF/Excel), copies it to the project's context directory,
-/// and commits it to Git. Sends progress updates via channel.
+/// Parses the file (CSV/PDF/Excel/Markdown/source code), checks its content
+/// hash against `context/.manifest.json` to skip re-uploading the same file
+/// under a new name, copies it to the project's context directory, and
+/// commits it to Git. Sends progress updates via channel.
 #[tauri::command]
 #[specta::specta]
 pub async fn upload_context_file(
@@ -324,22 +904,53 @@ pub async fn upload_context_file(
             return Err("File does not exist".to_string());
         }
 
-        // Parse file based on extension
+        // Classify by magic bytes (falling back to the extension only when
+        // the bytes carry no recognizable signature) so a mislabeled file
+        // can't sneak past the wrong parser.
         let extension = source_path
             .extension()
             .unwrap_or_default()
             .to_string_lossy()
             .to_lowercase();
 
-        let record = match extension.as_str() {
-            "csv" => parse_csv(&source_path)?,
-            "pdf" => parse_pdf(&source_path)?,
-            "xlsx" | "xls" => parse_excel(&source_path)?,
-            _ => return Err(format!("Unsupported file type: {extension}")),
+        let file_type = classify_file_type(&source_path, &extension)?;
+
+        let mut record = match file_type {
+            TypeOfFile::Csv => parse_csv(&source_path)?,
+            TypeOfFile::Pdf => parse_pdf(&source_path)?,
+            TypeOfFile::Excel => parse_excel(&source_path)?,
+            TypeOfFile::Text => parse_text(&source_path, &extension)?,
+            TypeOfFile::Zip | TypeOfFile::Image | TypeOfFile::Unknown => {
+                return Err(format!("Unsupported file type: {extension}"));
+            }
         };
 
+        if !record.integrity.ok {
+            log::warn!(
+                "{} failed integrity validation: {}",
+                record.original_filename, record.integrity.error_string
+            );
+            return Err(format!(
+                "{} failed integrity validation: {}",
+                record.original_filename, record.integrity.error_string
+            ));
+        }
+
         let _ = on_progress.send(UploadProgress::Parsing { percent: 50 });
 
+        if record.is_lfs && !detect_git_lfs().unwrap_or(false) {
+            log::warn!(
+                "{} exceeds the LFS threshold but Git LFS is not installed",
+                record.original_filename
+            );
+            let _ = on_progress.send(UploadProgress::Warning {
+                message: format!(
+                    "{} is large enough for Git LFS, but Git LFS isn't installed — it will be committed as an LFS pointer to a local object store instead of the real git-lfs CLI",
+                    record.original_filename
+                ),
+            });
+        }
+
         // Copy file to project context directory
         // For now, assume project_id is the directory path
         // TODO: Once project management is implemented, look up project path from ID
@@ -352,79 +963,75 @@ pub async fn upload_context_file(
             ));
         }
 
-        let dest_path = context_dir.join(&record.stored_filename);
-
-        // Check if file already exists
-        if dest_path.exists() {
-            return Err(format!(
-                "File {} already exists in project context",
-                record.stored_filename
-            ));
+        // Dedup against previous uploads by content hash, guarding the
+        // manifest read-modify-write against concurrent uploads.
+        let manifest_guard = MANIFEST_LOCK.lock().unwrap();
+        let mut manifest = load_context_manifest(&context_dir)?;
+
+        if let Some(existing) = manifest.get(&record.content_hash) {
+            let existing = existing.clone();
+            drop(manifest_guard);
+
+            log::info!(
+                "{} is a duplicate of already-uploaded {}, skipping copy/commit",
+                record.original_filename, existing.stored_filename
+            );
+            let _ = on_progress.send(UploadProgress::Duplicate {
+                record: existing.clone(),
+            });
+            return Ok(existing);
         }
 
-        let _ = on_progress.send(UploadProgress::Copying { percent: 60 });
-
-        // Copy file
-        fs::copy(&source_path, &dest_path)
-            .map_err(|e| format!("Failed to copy file to project: {e}"))?;
-
-        log::debug!("Copied file to {dest_path:?}");
+        // Different content reusing a stored name gets a -2, -3, ... suffix
+        record.stored_filename = resolve_unique_filename(&context_dir, &record.stored_filename);
+        record.relative_file_path = format!("context/{}", record.stored_filename);
 
-        let _ = on_progress.send(UploadProgress::Copying { percent: 80 });
-
-        // Git commit
-        let _ = on_progress.send(UploadProgress::Committing { percent: 90 });
+        let dest_path = context_dir.join(&record.stored_filename);
+        let relative_path = record.relative_file_path.clone();
 
-        let repo = Repository::open(&project_path)
-            .map_err(|e| format!("Failed to open Git repository: {e}"))?;
+        let _ = on_progress.send(UploadProgress::Copying { percent: 60 });
 
-        let mut index = repo
-            .index()
-            .map_err(|e| format!("Failed to get repository index: {e}"))?;
+        if record.is_lfs {
+            let content = fs::read(&source_path)
+                .map_err(|e| format!("Failed to read file for LFS storage: {e}"))?;
+            let pointer = store_lfs_object(&project_path, &content)?;
+            fs::write(&dest_path, &pointer.contents)
+                .map_err(|e| format!("Failed to write LFS pointer file: {e}"))?;
+            ensure_lfs_gitattributes_entry(&project_path, &relative_path)?;
 
-        // Stage the new file
-        let relative_path = format!("context/{}", record.stored_filename);
-        index
-            .add_path(Path::new(&relative_path))
-            .map_err(|e| format!("Failed to stage file: {e}"))?;
+            log::debug!("Wrote LFS pointer to {dest_path:?} (oid {})", pointer.oid);
+        } else {
+            fs::copy(&source_path, &dest_path)
+                .map_err(|e| format!("Failed to copy file to project: {e}"))?;
 
-        index
-            .write()
-            .map_err(|e| format!("Failed to write index: {e}"))?;
+            log::debug!("Copied file to {dest_path:?}");
+        }
 
-        // Create commit
-        let tree_id = index
-            .write_tree()
-            .map_err(|e| format!("Failed to write tree: {e}"))?;
+        let _ = on_progress.send(UploadProgress::Copying { percent: 80 });
 
-        let tree = repo
-            .find_tree(tree_id)
-            .map_err(|e| format!("Failed to find tree: {e}"))?;
+        manifest.insert(record.content_hash.clone(), record.clone());
+        save_context_manifest(&context_dir, &manifest)?;
+        drop(manifest_guard);
 
-        let signature = repo
-            .signature()
-            .or_else(|_| git2::Signature::now("Unheard User", "user@unheard.local"))
-            .map_err(|e| format!("Failed to create signature: {e}"))?;
+        // Git commit, routed through `git_auto_commit` so this gets the same
+        // isolated-tree-from-HEAD scoping, hook execution, signing, and
+        // identity fallback as every other save path (decisions.rs,
+        // experiments.rs, attio.rs) instead of hand-rolling an index/tree
+        // commit that could pick up unrelated staged or modified files.
+        let _ = on_progress.send(UploadProgress::Committing { percent: 90 });
 
-        let parent_commit = repo
-            .head()
-            .and_then(|head| head.peel_to_commit())
-            .map_err(|e| format!("Failed to get HEAD commit: {e}"))?;
+        let mut commit_files = vec![relative_path.clone(), "context/.manifest.json".to_string()];
+        if record.is_lfs {
+            commit_files.push(".gitattributes".to_string());
+        }
 
         let commit_message = format!(
             "Add context file: {}\n\nFile type: {}\nSize: {} bytes",
             record.original_filename, record.file_type, record.size_bytes
         );
 
-        repo.commit(
-            Some("HEAD"),
-            &signature,
-            &signature,
-            &commit_message,
-            &tree,
-            &[&parent_commit],
-        )
-        .map_err(|e| format!("Failed to create commit: {e}"))?;
+        git_auto_commit(project_path.clone(), commit_files, commit_message, true, false)
+            .map_err(|e| format!("Failed to create commit: {e}"))?;
 
         log::info!(
             "Created commit for context file: {}",
@@ -457,6 +1064,74 @@ mod tests {
         assert_eq!(sanitize_filename("simple.csv"), "simple.csv");
     }
 
+    #[test]
+    fn test_hash_file_contents_is_deterministic_and_content_addressed() {
+        let temp_dir = TempDir::new().unwrap();
+        let path_a = temp_dir.path().join("a.csv");
+        let path_b = temp_dir.path().join("b.csv");
+        fs::write(&path_a, "name,age\nAlice,30\n").unwrap();
+        fs::write(&path_b, "name,age\nAlice,30\n").unwrap();
+
+        let hash_a = hash_file_contents(&path_a).unwrap();
+        let hash_b = hash_file_contents(&path_b).unwrap();
+        assert_eq!(hash_a, hash_b);
+        assert_eq!(hash_a.len(), 64);
+
+        fs::write(&path_b, "name,age\nBob,25\n").unwrap();
+        let hash_b_changed = hash_file_contents(&path_b).unwrap();
+        assert_ne!(hash_a, hash_b_changed);
+    }
+
+    #[test]
+    fn test_resolve_unique_filename_appends_suffix_on_collision() {
+        let temp_dir = TempDir::new().unwrap();
+        let context_dir = temp_dir.path().to_path_buf();
+        fs::write(context_dir.join("report.csv"), "taken").unwrap();
+
+        assert_eq!(
+            resolve_unique_filename(&context_dir, "report.csv"),
+            "report-2.csv"
+        );
+
+        fs::write(context_dir.join("report-2.csv"), "also taken").unwrap();
+        assert_eq!(
+            resolve_unique_filename(&context_dir, "report.csv"),
+            "report-3.csv"
+        );
+    }
+
+    #[test]
+    fn test_resolve_unique_filename_passes_through_when_free() {
+        let temp_dir = TempDir::new().unwrap();
+        assert_eq!(
+            resolve_unique_filename(temp_dir.path(), "report.csv"),
+            "report.csv"
+        );
+    }
+
+    #[test]
+    fn test_context_manifest_round_trips_through_disk() {
+        let temp_dir = TempDir::new().unwrap();
+        let context_dir = temp_dir.path().to_path_buf();
+        fs::create_dir_all(&context_dir).unwrap();
+
+        assert!(load_context_manifest(&context_dir).unwrap().is_empty());
+
+        let csv_path = context_dir.join("customers.csv");
+        fs::write(&csv_path, "customer_id,name\n1,Alice\n").unwrap();
+        let record = parse_csv(&csv_path).unwrap();
+
+        let mut manifest = load_context_manifest(&context_dir).unwrap();
+        manifest.insert(record.content_hash.clone(), record.clone());
+        save_context_manifest(&context_dir, &manifest).unwrap();
+
+        let reloaded = load_context_manifest(&context_dir).unwrap();
+        assert_eq!(
+            reloaded.get(&record.content_hash).unwrap().stored_filename,
+            record.stored_filename
+        );
+    }
+
     #[test]
     fn test_parse_csv_basic() {
         let temp_dir = TempDir::new().unwrap();
@@ -544,4 +1219,232 @@ mod tests {
         let generic_cols = vec!["id".to_string(), "value".to_string()];
         assert_eq!(detect_csv_type(&generic_cols), None);
     }
+
+    #[test]
+    fn test_sniff_file_type_detects_pdf_regardless_of_extension() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("mislabeled.csv");
+        fs::write(&path, b"%PDF-1.4\n...").unwrap();
+
+        assert_eq!(sniff_file_type(&path).unwrap(), TypeOfFile::Pdf);
+    }
+
+    #[test]
+    fn test_sniff_file_type_returns_unknown_for_plain_text() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("data.csv");
+        fs::write(&path, "name,age\nAlice,30\n").unwrap();
+
+        assert_eq!(sniff_file_type(&path).unwrap(), TypeOfFile::Unknown);
+    }
+
+    #[test]
+    fn test_classify_file_type_falls_back_to_extension_hint() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("data.csv");
+        fs::write(&path, "name,age\nAlice,30\n").unwrap();
+
+        assert_eq!(classify_file_type(&path, "csv").unwrap(), TypeOfFile::Csv);
+    }
+
+    #[test]
+    fn test_classify_file_type_prefers_sniffed_type_over_mismatched_extension() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("report.csv");
+        fs::write(&path, b"%PDF-1.4\n...").unwrap();
+
+        assert_eq!(classify_file_type(&path, "csv").unwrap(), TypeOfFile::Pdf);
+    }
+
+    #[test]
+    fn test_has_zip_end_of_central_directory_detects_present_and_missing_record() {
+        let mut well_formed = vec![b'x'; 10];
+        well_formed.extend_from_slice(&[0x50, 0x4B, 0x05, 0x06]);
+        well_formed.extend_from_slice(&[0u8; 18]);
+        assert!(has_zip_end_of_central_directory(&well_formed));
+
+        let truncated = vec![b'x'; 30];
+        assert!(!has_zip_end_of_central_directory(&truncated));
+    }
+
+    #[test]
+    fn test_validate_csv_integrity_fails_on_ragged_rows() {
+        let content = "name,age,city\nAlice,30,NYC\nBob,25\n";
+
+        let integrity = validate_csv_integrity(content, b',');
+        assert!(!integrity.ok);
+        assert!(!integrity.error_string.is_empty());
+    }
+
+    #[test]
+    fn test_validate_csv_integrity_passes_on_well_formed_csv() {
+        let content = "name,age,city\nAlice,30,NYC\nBob,25,SF\n";
+
+        assert!(validate_csv_integrity(content, b',').ok);
+    }
+
+    #[test]
+    fn test_detect_csv_delimiter_picks_semicolon_for_european_export() {
+        let sample = "name;age;city\nAlice;30;NYC\nBob;25;SF\n";
+        assert_eq!(detect_csv_delimiter(sample), b';');
+    }
+
+    #[test]
+    fn test_detect_csv_delimiter_defaults_to_comma_for_single_column() {
+        let sample = "just_one_column\nvalue1\nvalue2\n";
+        assert_eq!(detect_csv_delimiter(sample), b',');
+    }
+
+    #[test]
+    fn test_decode_text_bytes_strips_utf8_bom() {
+        let mut bytes = vec![0xEF, 0xBB, 0xBF];
+        bytes.extend_from_slice(b"name,age\nAlice,30\n");
+
+        let (content, encoding) = decode_text_bytes(&bytes);
+        assert_eq!(encoding, "utf-8");
+        assert_eq!(content, "name,age\nAlice,30\n");
+    }
+
+    #[test]
+    fn test_decode_text_bytes_falls_back_to_windows_1252() {
+        // 0xE9 is "é" in Windows-1252/Latin-1 but invalid as a lone UTF-8 byte.
+        let mut bytes = b"nom,ville\n".to_vec();
+        bytes.extend_from_slice(&[
+            b'A', b'l', b'i', 0xE9, b'c', b'e', b',', b'P', b'a', b'r', b'i', 0xE9, b's', b'\n',
+        ]);
+
+        let (content, encoding) = decode_text_bytes(&bytes);
+        assert_eq!(encoding, "windows-1252");
+        assert!(content.contains('é'));
+    }
+
+    #[test]
+    fn test_validate_zip_integrity_fails_on_truncated_archive() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("broken.xlsx");
+        fs::write(&path, b"PK\x03\x04not a real archive").unwrap();
+
+        let integrity = validate_zip_integrity(&path, true);
+        assert!(!integrity.ok);
+    }
+
+    #[test]
+    fn test_validate_zip_integrity_skips_eocd_check_for_non_zip_legacy_xls() {
+        // Legacy OLE-based .xls isn't zip-backed, so the EOCD check shouldn't
+        // apply; calamine failing to open garbage bytes is still a failure,
+        // but it should come from the workbook-open step, not the EOCD check.
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("legacy.xls");
+        fs::write(&path, &[0xD0, 0xCF, 0x11, 0xE0, 0xA1, 0xB1, 0x1A, 0xE1]).unwrap();
+
+        let integrity = validate_zip_integrity(&path, true);
+        assert!(!integrity.ok);
+        assert!(!integrity.error_string.contains("end-of-central-directory"));
+    }
+
+    #[test]
+    fn test_parse_csv_sets_sniffed_type_and_integrity() {
+        let temp_dir = TempDir::new().unwrap();
+        let csv_path = temp_dir.path().join("clean.csv");
+        fs::write(&csv_path, "name,age\nAlice,30\nBob,25\n").unwrap();
+
+        let record = parse_csv(&csv_path).unwrap();
+
+        assert_eq!(record.sniffed_type, TypeOfFile::Csv);
+        assert!(record.integrity.ok);
+        assert_eq!(record.delimiter, Some(",".to_string()));
+        assert_eq!(record.encoding, Some("utf-8".to_string()));
+    }
+
+    #[test]
+    fn test_parse_csv_detects_semicolon_delimiter_and_stays_aligned_in_preview() {
+        let temp_dir = TempDir::new().unwrap();
+        let csv_path = temp_dir.path().join("european.csv");
+        fs::write(&csv_path, "nom;age;ville\nAlice;30;Paris\nBob;25;Lyon\n").unwrap();
+
+        let record = parse_csv(&csv_path).unwrap();
+
+        assert_eq!(record.delimiter, Some(";".to_string()));
+        assert_eq!(
+            record.columns,
+            Some(vec!["nom".to_string(), "age".to_string(), "ville".to_string()])
+        );
+        assert_eq!(record.preview.unwrap().lines().next().unwrap(), "nom;age;ville");
+    }
+
+    #[test]
+    fn test_parse_csv_flags_ragged_rows_as_failed_integrity() {
+        let temp_dir = TempDir::new().unwrap();
+        let csv_path = temp_dir.path().join("ragged.csv");
+        fs::write(&csv_path, "name,age,city\nAlice,30,NYC\nBob,25\n").unwrap();
+
+        let record = parse_csv(&csv_path).unwrap();
+
+        assert!(!record.integrity.ok);
+    }
+
+    #[test]
+    fn test_parse_text_renders_markdown_to_html() {
+        let temp_dir = TempDir::new().unwrap();
+        let md_path = temp_dir.path().join("notes.md");
+        fs::write(&md_path, "# Heading\n\nSome *text*.").unwrap();
+
+        let record = parse_text(&md_path, "md").unwrap();
+
+        assert_eq!(record.file_type, "markdown");
+        assert_eq!(record.sniffed_type, TypeOfFile::Text);
+        assert!(record.html_preview.unwrap().contains("<h1>Heading</h1>"));
+        assert!(record.text_preview.is_none());
+    }
+
+    #[test]
+    fn test_parse_text_syntax_highlights_recognized_source() {
+        let temp_dir = TempDir::new().unwrap();
+        let rs_path = temp_dir.path().join("main.rs");
+        fs::write(&rs_path, "fn main() {\n    println!(\"hi\");\n}\n").unwrap();
+
+        let record = parse_text(&rs_path, "rs").unwrap();
+
+        assert_eq!(record.file_type, "rust");
+        assert!(record.html_preview.unwrap().contains("class=\"highlight\""));
+        assert!(record.text_preview.is_none());
+    }
+
+    #[test]
+    fn test_parse_text_falls_back_to_plain_preview_for_unrecognized_extension() {
+        let temp_dir = TempDir::new().unwrap();
+        let log_path = temp_dir.path().join("notes.txt");
+        fs::write(&log_path, "just some plain notes").unwrap();
+
+        let record = parse_text(&log_path, "txt").unwrap();
+
+        assert_eq!(record.file_type, "text");
+        assert_eq!(record.text_preview, Some("just some plain notes".to_string()));
+        assert!(record.html_preview.is_none());
+    }
+
+    #[test]
+    fn test_render_text_preview_caches_by_filename_and_content_hash() {
+        let first = render_text_preview("doc.md", "hash-1", "# First", "md");
+        // Different content under the same cache key should still return the
+        // cached (stale) render rather than re-rendering, since the cache key
+        // is content-addressed by hash, not by live content.
+        let second = render_text_preview("doc.md", "hash-1", "# Second", "md");
+
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn test_classify_file_type_routes_markdown_and_source_extensions_to_text() {
+        let temp_dir = TempDir::new().unwrap();
+        let md_path = temp_dir.path().join("readme.md");
+        fs::write(&md_path, "# Title").unwrap();
+
+        assert_eq!(classify_file_type(&md_path, "md").unwrap(), TypeOfFile::Text);
+
+        let py_path = temp_dir.path().join("script.py");
+        fs::write(&py_path, "print('hi')").unwrap();
+
+        assert_eq!(classify_file_type(&py_path, "py").unwrap(), TypeOfFile::Text);
+    }
 }
@@ -3,6 +3,7 @@
 //! Handles automatic creation of decision log markdown files and Git commits.
 
 use crate::commands::git::git_auto_commit;
+use crate::commands::index;
 use std::fs;
 use std::path::PathBuf;
 
@@ -61,15 +62,30 @@ pub fn create_decision_log(
 
     // Commit to Git
     let commit_message = format!("Create decision: {filename}");
-    match git_auto_commit(project_path, vec![relative_path.clone()], commit_message) {
+    let commit_hash = match git_auto_commit(
+        project_path.clone(),
+        vec![relative_path.clone()],
+        commit_message,
+        true,
+        false,
+    ) {
         Ok(commit_hash) => {
             log::info!("Decision log committed: {commit_hash}");
+            Some(commit_hash)
         }
         Err(e) => {
             log::error!("Git commit failed: {e}");
             log::warn!("Decision log saved but not committed");
             // Don't fail the operation - file was saved successfully
+            None
         }
+    };
+
+    // Best-effort: indexing failures are logged but never fail the save.
+    if let Err(e) =
+        index::index_decision(&project_path, &relative_path, &content, commit_hash.as_deref())
+    {
+        log::error!("Failed to index decision log {relative_path}: {e}");
     }
 
     Ok(relative_path)
@@ -242,4 +258,35 @@ This is a test decision log.
         let file_path = non_repo_path.join("decisions/test.md");
         assert!(file_path.exists());
     }
+
+    #[test]
+    fn test_create_decision_log_indexes_frontmatter() {
+        let (_temp_dir, repo_path) = create_test_repo();
+
+        let content = r#"---
+id: dec-test-1
+title: Test Decision
+status: ready
+date: 2026-02-04
+---
+
+# Test Decision
+"#;
+
+        let relative_path = create_decision_log(
+            content.to_string(),
+            "2026-02-04-test-decision.md".to_string(),
+            repo_path.clone(),
+        )
+        .unwrap();
+
+        let indexed = index::list_decisions(repo_path, None).unwrap();
+        let entry = indexed
+            .iter()
+            .find(|d| d.relative_path == relative_path)
+            .expect("decision should be indexed");
+        assert_eq!(entry.title, Some("Test Decision".to_string()));
+        assert_eq!(entry.status, Some("ready".to_string()));
+        assert!(entry.commit_hash.is_some());
+    }
 }
@@ -2,12 +2,18 @@
 //!
 //! Handles writing experiment config YAML files and auto-committing to Git.
 
-use crate::commands::git::git_auto_commit;
+use crate::commands::git::{get_file_at_commit, get_file_history, git_auto_commit, FileHistoryEntry};
+use git2::{build::CheckoutBuilder, Repository};
 use std::fs;
 use std::path::PathBuf;
 
 /// Write an experiment config YAML file and commit it to Git.
 ///
+/// Commits with `amend` set (see `git_auto_commit`). Because an existing
+/// filename is saved under a new `-2`, `-3`, ... name, a repeated save of the
+/// same filename lands in a new file and gets its own commit rather than
+/// amending the previous one.
+///
 /// # Arguments
 /// * `project_path` - Path to the project root (Git repository)
 /// * `filename` - Filename (e.g., "2026-02-06-seed-fundraising.yaml")
@@ -65,7 +71,7 @@ pub fn write_experiment_config(
 
     // Commit to Git
     let commit_message = format!("[unheard] Add experiment config: {final_filename}");
-    match git_auto_commit(project, vec![relative_path.clone()], commit_message) {
+    match git_auto_commit(project, vec![relative_path.clone()], commit_message, true, true) {
         Ok(commit_hash) => {
             log::info!("Experiment config committed: {commit_hash}");
         }
@@ -79,6 +85,92 @@ pub fn write_experiment_config(
     Ok(relative_path)
 }
 
+/// List every commit that touched an experiment config file, most recent
+/// first, so the UI can present a version picker.
+///
+/// # Arguments
+/// * `project_path` - Path to the project root (Git repository)
+/// * `relative_path` - Path relative to repo root (e.g., "experiments/foo.yaml")
+#[tauri::command]
+#[specta::specta]
+pub fn list_experiment_config_history(
+    project_path: String,
+    relative_path: String,
+) -> Result<Vec<FileHistoryEntry>, String> {
+    get_file_history(PathBuf::from(project_path), relative_path)
+}
+
+/// Restore an experiment config file to an earlier version.
+///
+/// With `commit_id` set to `None`, discards uncommitted working-tree changes
+/// for `relative_path` by checking it back out from `HEAD`. With `commit_id`
+/// set, writes that commit's version of the file back to disk and re-commits
+/// it via `git_auto_commit`, so the restore itself is recorded in history.
+///
+/// # Arguments
+/// * `project_path` - Path to the project root (Git repository)
+/// * `relative_path` - Path relative to repo root (e.g., "experiments/foo.yaml")
+/// * `commit_id` - Commit to restore from, or `None` to discard local edits
+#[tauri::command]
+#[specta::specta]
+pub fn restore_experiment_config(
+    project_path: String,
+    relative_path: String,
+    commit_id: Option<String>,
+) -> Result<(), String> {
+    log::info!("Restoring experiment config {relative_path} in {project_path}");
+
+    if relative_path.trim().is_empty() {
+        return Err("Relative path cannot be empty".to_string());
+    }
+
+    let project = PathBuf::from(&project_path);
+
+    let Some(commit_id) = commit_id else {
+        let repo = Repository::open(&project).map_err(|e| {
+            log::error!("Failed to open Git repository: {e}");
+            format!("Failed to open Git repository: {e}")
+        })?;
+
+        let mut checkout = CheckoutBuilder::new();
+        checkout
+            .path(relative_path.as_str())
+            .force()
+            .update_index(true)
+            .remove_untracked(false);
+
+        repo.checkout_head(Some(&mut checkout)).map_err(|e| {
+            log::error!("Failed to discard changes to {relative_path}: {e}");
+            format!("Failed to discard changes to {relative_path}: {e}")
+        })?;
+
+        log::info!("Discarded working-tree changes to {relative_path}");
+        return Ok(());
+    };
+
+    let content = get_file_at_commit(project.clone(), relative_path.clone(), commit_id.clone())?;
+
+    let file_path = project.join(&relative_path);
+    fs::write(&file_path, &content).map_err(|e| {
+        log::error!("Failed to write restored {relative_path}: {e}");
+        format!("Failed to write restored {relative_path}: {e}")
+    })?;
+
+    let commit_message = format!("[unheard] Restore {relative_path} to {commit_id}");
+    match git_auto_commit(project, vec![relative_path.clone()], commit_message, true, false) {
+        Ok(commit_hash) => {
+            log::info!("Restore committed: {commit_hash}");
+        }
+        Err(e) => {
+            log::error!("Git commit failed: {e}");
+            log::warn!("Experiment config restored but not committed");
+            // Don't fail the operation - file was restored successfully
+        }
+    }
+
+    Ok(())
+}
+
 /// Resolve a unique filename by appending -2, -3, etc. if the file already exists.
 ///
 /// Given "2026-02-06-seed-fundraising.yaml", checks if it exists and returns
@@ -315,6 +407,114 @@ mod tests {
         assert!(file_path.exists());
     }
 
+    #[test]
+    fn test_list_experiment_config_history_tracks_edits() {
+        let (_temp_dir, repo_path) = create_test_repo();
+
+        write_experiment_config(
+            repo_path.to_string_lossy().to_string(),
+            "test.yaml".to_string(),
+            "metadata: {id: test, version: 1}".to_string(),
+        )
+        .unwrap();
+
+        let history = list_experiment_config_history(
+            repo_path.to_string_lossy().to_string(),
+            "experiments/test.yaml".to_string(),
+        )
+        .unwrap();
+
+        assert_eq!(history.len(), 1);
+        assert_eq!(
+            history[0].summary,
+            "[unheard] Add experiment config: test.yaml"
+        );
+    }
+
+    #[test]
+    fn test_restore_experiment_config_discards_local_edits() {
+        let (_temp_dir, repo_path) = create_test_repo();
+
+        write_experiment_config(
+            repo_path.to_string_lossy().to_string(),
+            "test.yaml".to_string(),
+            "metadata: {id: test, version: 1}".to_string(),
+        )
+        .unwrap();
+
+        let file_path = repo_path.join("experiments/test.yaml");
+        fs::write(&file_path, "metadata: {id: test, version: tampered}").unwrap();
+
+        restore_experiment_config(
+            repo_path.to_string_lossy().to_string(),
+            "experiments/test.yaml".to_string(),
+            None,
+        )
+        .unwrap();
+
+        let content = fs::read_to_string(&file_path).unwrap();
+        assert_eq!(content, "metadata: {id: test, version: 1}");
+    }
+
+    #[test]
+    fn test_restore_experiment_config_from_earlier_commit() {
+        let (_temp_dir, repo_path) = create_test_repo();
+
+        write_experiment_config(
+            repo_path.to_string_lossy().to_string(),
+            "test.yaml".to_string(),
+            "metadata: {id: test, version: 1}".to_string(),
+        )
+        .unwrap();
+
+        let repo = Repository::open(&repo_path).unwrap();
+        let first_commit_id = repo.head().unwrap().target().unwrap().to_string();
+
+        fs::write(
+            repo_path.join("experiments/test.yaml"),
+            "metadata: {id: test, version: 2}",
+        )
+        .unwrap();
+        git_auto_commit(
+            repo_path.clone(),
+            vec!["experiments/test.yaml".to_string()],
+            "[unheard] Update experiment config: test.yaml".to_string(),
+            true,
+            false,
+        )
+        .unwrap();
+
+        restore_experiment_config(
+            repo_path.to_string_lossy().to_string(),
+            "experiments/test.yaml".to_string(),
+            Some(first_commit_id),
+        )
+        .unwrap();
+
+        let file_path = repo_path.join("experiments/test.yaml");
+        let content = fs::read_to_string(&file_path).unwrap();
+        assert_eq!(content, "metadata: {id: test, version: 1}");
+
+        // The restore itself should be recorded as a new commit.
+        let repo = Repository::open(&repo_path).unwrap();
+        let head_commit = repo.head().unwrap().peel_to_commit().unwrap();
+        assert!(head_commit.message().unwrap().starts_with("[unheard] Restore"));
+    }
+
+    #[test]
+    fn test_restore_experiment_config_empty_path() {
+        let (_temp_dir, repo_path) = create_test_repo();
+
+        let result = restore_experiment_config(
+            repo_path.to_string_lossy().to_string(),
+            "".to_string(),
+            None,
+        );
+
+        assert!(result.is_err());
+        assert!(result.unwrap_err().contains("Relative path cannot be empty"));
+    }
+
     #[test]
     fn test_resolve_unique_filename_no_conflict() {
         let temp_dir = TempDir::new().unwrap();
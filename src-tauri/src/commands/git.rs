@@ -1,20 +1,864 @@
-//! Git auto-commit commands.
+//! Git auto-commit and identity commands.
 //!
-//! Handles automatic Git commits for uploaded context files.
+//! Handles automatic Git commits for uploaded context files, per-file Git
+//! status, and reading/writing the Git identity used to author them.
 //! LFS tracking is handled via .gitattributes rules (created during project initialization).
 
-use git2::{Repository, Signature};
-use std::path::PathBuf;
+use crate::types::validate_string_input;
+use git2::{
+    Commit, Config, Oid, Repository, RepositoryOpenFlags, Signature, Status, StatusOptions, Tree,
+};
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, LazyLock, Mutex};
+use std::time::{Duration, Instant};
+
+// ============================================================================
+// Per-file status cache
+// ============================================================================
+
+/// How long a cached status snapshot remains valid before being rebuilt.
+const STATUS_CACHE_TTL: Duration = Duration::from_secs(2);
+
+struct StatusCacheEntry {
+    fetched_at: Instant,
+    statuses: Arc<HashMap<PathBuf, Status>>,
+}
+
+/// Process-lifetime cache of per-path Git statuses, keyed by repository
+/// working directory. Mirrors the global `GitCache` pattern used by tools
+/// like `exa`/`lsd` so that repeatedly listing a large `context/` tree
+/// doesn't re-discover and re-diff the repository on every call.
+static STATUS_CACHE: LazyLock<Mutex<HashMap<PathBuf, StatusCacheEntry>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Look up the Git status of every changed or untracked path under
+/// `project_path`, keyed by path relative to the repository root.
+///
+/// Discovers the repository via `Repository::discover` so this also works
+/// from a subdirectory of the repo. Results are cached per repository root
+/// for [`STATUS_CACHE_TTL`]; callers that need a fresh snapshot (e.g. right
+/// after a commit) should be aware lookups may lag by a couple of seconds.
+pub fn repo_statuses(project_path: &Path) -> Result<Arc<HashMap<PathBuf, Status>>, String> {
+    let repo = Repository::discover(project_path)
+        .map_err(|e| format!("Failed to discover Git repository: {e}"))?;
+    let repo_root = repo
+        .workdir()
+        .unwrap_or_else(|| repo.path())
+        .to_path_buf();
+
+    if let Some(entry) = STATUS_CACHE.lock().unwrap().get(&repo_root) {
+        if entry.fetched_at.elapsed() < STATUS_CACHE_TTL {
+            return Ok(entry.statuses.clone());
+        }
+    }
+
+    let mut opts = StatusOptions::new();
+    opts.include_untracked(true).recurse_untracked_dirs(true);
+
+    let statuses = repo
+        .statuses(Some(&mut opts))
+        .map_err(|e| format!("Failed to read Git status: {e}"))?;
+
+    let mut map = HashMap::with_capacity(statuses.len());
+    for entry in statuses.iter() {
+        if let Some(path) = entry.path() {
+            map.insert(PathBuf::from(path), entry.status());
+        }
+    }
+    let map = Arc::new(map);
+
+    STATUS_CACHE.lock().unwrap().insert(
+        repo_root,
+        StatusCacheEntry {
+            fetched_at: Instant::now(),
+            statuses: map.clone(),
+        },
+    );
+
+    Ok(map)
+}
+
+// ============================================================================
+// Git identity
+// ============================================================================
+
+/// A `user.name`/`user.email` pair read from Git configuration.
+/// Either field may be absent if that key was never configured.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct GitIdentity {
+    pub name: Option<String>,
+    pub email: Option<String>,
+}
+
+/// Very loose email shape check: an `@`, with at least one character on
+/// either side of the first one and a `.` somewhere after it. Not meant to be a strict RFC
+/// 5322 validator, just enough to catch obvious typos before they end up in
+/// commit authorship.
+fn validate_email_shape(email: &str) -> Result<(), String> {
+    let Some((local, domain)) = email.split_once('@') else {
+        return Err("Invalid email: must contain '@'".to_string());
+    };
+    if local.is_empty() || domain.is_empty() || !domain.contains('.') {
+        return Err("Invalid email: must look like name@domain.tld".to_string());
+    }
+    Ok(())
+}
+
+/// Read the configured Git identity from the global (`~/.gitconfig`) config.
+#[tauri::command]
+#[specta::specta]
+pub fn get_git_identity() -> Result<GitIdentity, String> {
+    let config = Config::open_default().map_err(|e| {
+        log::error!("Failed to open global Git config: {e}");
+        format!("Failed to open global Git config: {e}")
+    })?;
+
+    let name = config.get_string("user.name").ok();
+    let email = config.get_string("user.email").ok();
+
+    Ok(GitIdentity { name, email })
+}
+
+/// Write `user.name`/`user.email` to the global Git config.
+///
+/// Validates both the shape of the email address and the length of the
+/// inputs before writing, so a typo doesn't silently poison the commit
+/// authorship used by [`initialize_git`](crate::commands::projects::initialize_git)
+/// and every `git_auto_commit` call that follows.
+#[tauri::command]
+#[specta::specta]
+pub fn set_git_identity(name: String, email: String) -> Result<(), String> {
+    validate_string_input(&name, 100, "Name")?;
+    validate_string_input(&email, 254, "Email")?;
+    validate_email_shape(&email)?;
+
+    let mut config = Config::open_default().map_err(|e| {
+        log::error!("Failed to open global Git config: {e}");
+        format!("Failed to open global Git config: {e}")
+    })?;
+
+    config.set_str("user.name", &name).map_err(|e| {
+        log::error!("Failed to set user.name: {e}");
+        format!("Failed to set user.name: {e}")
+    })?;
+
+    config.set_str("user.email", &email).map_err(|e| {
+        log::error!("Failed to set user.email: {e}");
+        format!("Failed to set user.email: {e}")
+    })?;
+
+    log::info!("Updated global Git identity: {name} <{email}>");
+    Ok(())
+}
+
+/// Open the Git config to read/write an identity from: the repo-local
+/// `.git/config` by default, or the global `~/.gitconfig` when `global` is
+/// set. Unlike `repo.config()`'s merged view (repo + global + system), this
+/// is only used by [`git_get_identity`]/[`git_set_identity`], which need to
+/// target one scope explicitly rather than read the effective merged value.
+fn identity_config(repo_path: &Path, global: bool) -> Result<Config, String> {
+    if global {
+        Config::open_default().map_err(|e| {
+            log::error!("Failed to open global Git config: {e}");
+            format!("Failed to open global Git config: {e}")
+        })
+    } else {
+        let repo = Repository::open(repo_path).map_err(|e| {
+            log::error!("Failed to open Git repository at {repo_path:?}: {e}");
+            format!("Failed to open Git repository: {e}")
+        })?;
+        repo.config().and_then(|c| c.open_level(git2::ConfigLevel::Local)).map_err(|e| {
+            log::error!("Failed to open repo Git config: {e}");
+            format!("Failed to open repo Git config: {e}")
+        })
+    }
+}
+
+/// Read the Git identity configured for `repo_path`'s repository (`global:
+/// false`) or the global `~/.gitconfig` (`global: true`), for the
+/// preferences UI to show alongside project settings.
+#[tauri::command]
+#[specta::specta]
+pub fn git_get_identity(repo_path: PathBuf, global: bool) -> Result<GitIdentity, String> {
+    let config = identity_config(&repo_path, global)?;
+    Ok(GitIdentity {
+        name: config.get_string("user.name").ok(),
+        email: config.get_string("user.email").ok(),
+    })
+}
+
+/// Write `user.name`/`user.email` to `repo_path`'s repo-local config
+/// (`global: false`) or the global `~/.gitconfig` (`global: true`), so the
+/// preferences UI can set a per-project identity without touching the
+/// user's global one unless they ask for that scope.
+#[tauri::command]
+#[specta::specta]
+pub fn git_set_identity(
+    repo_path: PathBuf,
+    global: bool,
+    name: String,
+    email: String,
+) -> Result<(), String> {
+    validate_string_input(&name, 100, "Name")?;
+    validate_string_input(&email, 254, "Email")?;
+    validate_email_shape(&email)?;
+
+    let mut config = identity_config(&repo_path, global)?;
+
+    config.set_str("user.name", &name).map_err(|e| {
+        log::error!("Failed to set user.name: {e}");
+        format!("Failed to set user.name: {e}")
+    })?;
+
+    config.set_str("user.email", &email).map_err(|e| {
+        log::error!("Failed to set user.email: {e}");
+        format!("Failed to set user.email: {e}")
+    })?;
+
+    log::info!(
+        "Updated {} Git identity: {name} <{email}>",
+        if global { "global" } else { "repo" }
+    );
+    Ok(())
+}
+
+/// Where the signature used for a commit actually came from, so the UI can
+/// prompt the user to configure their identity before the first real commit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub enum GitIdentitySource {
+    /// `repo.signature()` resolved successfully (repo-local or global config).
+    Repo,
+    /// Repo signature resolution failed; the global config was read directly.
+    Global,
+    /// Neither source had a usable identity; a synthetic placeholder was used.
+    Synthetic,
+}
+
+/// Resolve the signature to use for a freshly initialized repository,
+/// trying the repo's own (repo-local + global + system) configuration
+/// first, then falling back to an explicit read of the global config, and
+/// only using a synthetic placeholder as a last resort.
+pub fn resolve_init_signature(repo: &Repository) -> (Signature<'static>, GitIdentitySource) {
+    if let Ok(sig) = repo.signature() {
+        return (sig, GitIdentitySource::Repo);
+    }
+
+    if let Ok(config) = Config::open_default() {
+        if let (Ok(name), Ok(email)) = (config.get_string("user.name"), config.get_string("user.email")) {
+            if let Ok(sig) = Signature::now(&name, &email) {
+                return (sig, GitIdentitySource::Global);
+            }
+        }
+    }
+
+    log::warn!("No Git identity configured, using synthetic signature");
+    let sig = Signature::now("Unheard User", "user@unheard.local")
+        .expect("synthetic signature is always valid");
+    (sig, GitIdentitySource::Synthetic)
+}
+
+/// Insert `blob_oid` at the path described by `components` into a tree
+/// built on top of `base` (if any), creating intermediate trees recursively
+/// for nested paths like `experiments/foo.yaml`. Returns the resulting tree
+/// OID; `base`'s other entries are carried over untouched.
+fn insert_blob_at_path(
+    repo: &Repository,
+    base: Option<&Tree>,
+    components: &[&str],
+    blob_oid: Oid,
+) -> Result<Oid, git2::Error> {
+    let mut builder = repo.treebuilder(base)?;
+
+    if let [name] = components {
+        builder.insert(name, blob_oid, 0o100644)?;
+    } else {
+        let name = components[0];
+        let existing_subtree = base
+            .and_then(|tree| tree.get_name(name))
+            .and_then(|entry| entry.to_object(repo).ok())
+            .and_then(|object| object.into_tree().ok());
+        let sub_oid =
+            insert_blob_at_path(repo, existing_subtree.as_ref(), &components[1..], blob_oid)?;
+        builder.insert(name, sub_oid, 0o040000)?;
+    }
+
+    builder.write()
+}
+
+/// Build a tree that starts from `base_tree` (the repository's current HEAD
+/// tree, or `None` on an unborn branch) and overlays exactly `files`,
+/// regardless of whatever else happens to be staged or modified in the
+/// working index. This is what keeps `git_auto_commit` reproducible: the
+/// resulting commit contains only the files the caller asked for.
+fn write_isolated_tree(
+    repo: &Repository,
+    base_tree: Option<Tree>,
+    files: &[(String, Vec<u8>)],
+) -> Result<Oid, git2::Error> {
+    let mut tree = base_tree;
+    let mut tree_oid = tree.as_ref().map(Tree::id);
+
+    for (relative_path, content) in files {
+        let blob_oid = repo.blob(content)?;
+        let components: Vec<&str> = relative_path.split('/').collect();
+        let new_oid = insert_blob_at_path(repo, tree.as_ref(), &components, blob_oid)?;
+        tree_oid = Some(new_oid);
+        tree = Some(repo.find_tree(new_oid)?);
+    }
+
+    // `files` is never empty (checked by `git_auto_commit` before this is
+    // called), so `tree_oid` is always set by the loop above.
+    Ok(tree_oid.expect("write_isolated_tree called with no files"))
+}
+
+/// Whether `commit`'s own changes (relative to its first parent, or to an
+/// empty tree if it has none) touch exactly `files` and nothing else. This
+/// is the condition `git_auto_commit` uses to decide that a save amends the
+/// same logical change rather than starting a new one.
+fn commit_touches_exactly(repo: &Repository, commit: &Commit, files: &[String]) -> bool {
+    let Ok(tree) = commit.tree() else {
+        return false;
+    };
+    let parent_tree = commit.parent(0).ok().and_then(|parent| parent.tree().ok());
+
+    let Ok(diff) = repo.diff_tree_to_tree(parent_tree.as_ref(), Some(&tree), None) else {
+        return false;
+    };
+
+    let mut changed: HashSet<String> = HashSet::new();
+    let walked = diff.foreach(
+        &mut |delta, _| {
+            if let Some(path) = delta.new_file().path().and_then(Path::to_str) {
+                changed.insert(path.to_string());
+            }
+            true
+        },
+        None,
+        None,
+        None,
+    );
+
+    if walked.is_err() {
+        return false;
+    }
+
+    let wanted: HashSet<String> = files.iter().cloned().collect();
+    changed == wanted
+}
+
+/// Resolve the signature `git_auto_commit` authors with, in three tiers: the
+/// repo's own fully-configured identity (`repo.signature()`, covering
+/// repo-local + global + system config); if that's only missing (not merely
+/// malformed) and `user.email` is set, a signature pairing it with a
+/// placeholder `"unknown"` name rather than silently attributing the commit
+/// to the app's own identity; and only then the app's synthetic default.
+/// This is what keeps a decision-log or Attio-import save from silently
+/// downgrading to "saved but not committed" on a machine where `user.name`
+/// was never configured.
+///
+/// A `repo.signature()` failure that *isn't* a missing identity (e.g. a
+/// corrupt config value) is propagated rather than papered over, so that
+/// genuine config problems still surface instead of being masked by the
+/// synthetic fallback.
+fn signature_allow_undefined_name(repo: &Repository) -> Result<Signature<'static>, git2::Error> {
+    match repo.signature() {
+        Ok(sig) => return Ok(sig),
+        Err(e) if e.code() == git2::ErrorCode::NotFound => {}
+        Err(e) => return Err(e),
+    }
+
+    let email = repo
+        .config()
+        .ok()
+        .and_then(|config| config.get_string("user.email").ok());
+
+    if let Some(email) = email {
+        if let Ok(sig) = Signature::now("unknown", &email) {
+            return Ok(sig);
+        }
+    }
+
+    log::debug!("Git user not configured, using default signature");
+    Signature::now("Unheard User", "user@unheard.local")
+}
+
+// ============================================================================
+// Commit hooks
+// ============================================================================
+
+/// Resolve the directory Git hooks live in: `core.hooksPath` if configured
+/// (relative paths are resolved against the repository's working directory),
+/// otherwise the repo's default `<gitdir>/hooks`.
+fn hooks_dir(repo: &Repository) -> PathBuf {
+    let configured = repo
+        .config()
+        .ok()
+        .and_then(|config| config.get_string("core.hooksPath").ok());
+
+    match configured {
+        Some(path) if Path::new(&path).is_absolute() => PathBuf::from(path),
+        Some(path) => repo.workdir().unwrap_or_else(|| repo.path()).join(path),
+        None => repo.path().join("hooks"),
+    }
+}
+
+/// Whether `path` exists and is executable. On Unix this checks the
+/// executable permission bits, matching Git's own rule for which hook
+/// scripts it will run.
+fn is_executable(path: &Path) -> bool {
+    let Ok(metadata) = std::fs::metadata(path) else {
+        return false;
+    };
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        metadata.is_file() && metadata.permissions().mode() & 0o111 != 0
+    }
+    #[cfg(not(unix))]
+    {
+        metadata.is_file()
+    }
+}
+
+/// Run `hook_name` from `hooks_dir` with `args`, if it exists and is
+/// executable; hooks that aren't present or aren't executable are silently
+/// skipped, matching Git's own behavior. Returns the hook's combined
+/// stdout+stderr on success, or `Err` with the same combined output if it
+/// exits non-zero.
+fn run_hook(
+    hooks_dir: &Path,
+    hook_name: &str,
+    workdir: &Path,
+    args: &[&str],
+) -> Result<Option<String>, String> {
+    let hook_path = hooks_dir.join(hook_name);
+    if !is_executable(&hook_path) {
+        return Ok(None);
+    }
+
+    log::debug!("Running {hook_name} hook: {hook_path:?}");
+    let output = std::process::Command::new(&hook_path)
+        .args(args)
+        .current_dir(workdir)
+        .output()
+        .map_err(|e| format!("Failed to run {hook_name} hook: {e}"))?;
+
+    let combined = format!(
+        "{}{}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    if !output.status.success() {
+        log::error!("{hook_name} hook failed: {combined}");
+        return Err(combined);
+    }
+
+    Ok(Some(combined))
+}
+
+/// Unique path for the temp file handed to the `commit-msg` hook, which may
+/// rewrite it in place the same way Git itself lets the hook do.
+fn commit_msg_temp_path() -> PathBuf {
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_nanos())
+        .unwrap_or_default();
+    std::env::temp_dir().join(format!("unheard-commit-msg-{}-{nanos}", std::process::id()))
+}
+
+/// Run the `commit-msg` hook, if present, and return the (possibly
+/// rewritten) commit message. Git passes the hook a path to a temp file
+/// containing the message; the hook may edit that file in place, so we
+/// read it back afterward rather than assuming it's unchanged.
+fn run_commit_msg_hook(hooks_dir: &Path, workdir: &Path, message: &str) -> Result<String, String> {
+    if !is_executable(&hooks_dir.join("commit-msg")) {
+        return Ok(message.to_string());
+    }
+
+    let msg_path = commit_msg_temp_path();
+    std::fs::write(&msg_path, message)
+        .map_err(|e| format!("Failed to write commit message temp file: {e}"))?;
+
+    let msg_path_str = msg_path.to_string_lossy().to_string();
+    let result = run_hook(hooks_dir, "commit-msg", workdir, &[&msg_path_str]);
+    let rewritten = std::fs::read_to_string(&msg_path);
+    let _ = std::fs::remove_file(&msg_path);
+
+    result?;
+    rewritten.map_err(|e| format!("Failed to read commit message back from commit-msg hook: {e}"))
+}
+
+// ============================================================================
+// Commit signing
+// ============================================================================
+
+/// Commit-signing configuration read from repo config: `commit.gpgsign`,
+/// `user.signingkey`, `gpg.format` (`"openpgp"` or `"ssh"`, defaulting to
+/// `"openpgp"` to match Git's own default), and -- for the SSH format --
+/// `gpg.ssh.allowedSignersFile`, the same trust file `git verify-commit`
+/// itself consults to check an SSH signature's key against a known identity.
+struct SigningConfig {
+    enabled: bool,
+    signing_key: Option<String>,
+    format: String,
+    allowed_signers_file: Option<String>,
+}
+
+fn signing_config(repo: &Repository) -> SigningConfig {
+    let config = repo.config().ok();
+    let enabled = config
+        .as_ref()
+        .and_then(|config| config.get_bool("commit.gpgsign").ok())
+        .unwrap_or(false);
+    let signing_key = config
+        .as_ref()
+        .and_then(|config| config.get_string("user.signingkey").ok());
+    let format = config
+        .as_ref()
+        .and_then(|config| config.get_string("gpg.format").ok())
+        .unwrap_or_else(|| "openpgp".to_string());
+    let allowed_signers_file = config
+        .as_ref()
+        .and_then(|config| config.get_string("gpg.ssh.allowedSignersFile").ok());
+
+    SigningConfig { enabled, signing_key, format, allowed_signers_file }
+}
+
+/// Write `content` to a uniquely-named file under the system temp dir, for
+/// handing off to the `gpg`/`ssh-keygen` subprocesses below (both tools sign
+/// and verify files, not stdin streams, for detached signatures).
+fn write_signing_temp_file(label: &str, content: &str) -> Result<PathBuf, String> {
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_nanos())
+        .unwrap_or_default();
+    let path = std::env::temp_dir().join(format!("unheard-{label}-{}-{nanos}", std::process::id()));
+    std::fs::write(&path, content)
+        .map_err(|e| format!("Failed to write {label} temp file: {e}"))?;
+    Ok(path)
+}
+
+/// Detach-sign `buffer` (a commit's serialized content, as produced by
+/// `Repository::commit_create_buffer`) using `signing`'s configured key and
+/// format. Returns the armored signature text to embed as the commit's
+/// `gpgsig` header.
+fn sign_commit_buffer(buffer: &str, signing: &SigningConfig) -> Result<String, String> {
+    let Some(key) = &signing.signing_key else {
+        return Err("commit.gpgsign is enabled but user.signingkey is not set".to_string());
+    };
+
+    match signing.format.as_str() {
+        "ssh" => sign_with_ssh_keygen(buffer, key),
+        _ => sign_with_gpg(buffer, key),
+    }
+}
+
+/// Sign `buffer` with `gpg --detach-sign --armor`, the OpenPGP path.
+fn sign_with_gpg(buffer: &str, key: &str) -> Result<String, String> {
+    use std::io::Write;
+
+    let mut child = std::process::Command::new("gpg")
+        .args(["--detach-sign", "--armor", "--local-user", key])
+        .stdin(std::process::Stdio::piped())
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("Failed to run gpg: {e}"))?;
+
+    child
+        .stdin
+        .take()
+        .expect("gpg stdin is piped")
+        .write_all(buffer.as_bytes())
+        .map_err(|e| format!("Failed to write commit buffer to gpg: {e}"))?;
+
+    let output = child
+        .wait_with_output()
+        .map_err(|e| format!("Failed to wait for gpg: {e}"))?;
+
+    if !output.status.success() {
+        return Err(format!(
+            "gpg --detach-sign failed: {}",
+            String::from_utf8_lossy(&output.stderr)
+        ));
+    }
+
+    String::from_utf8(output.stdout).map_err(|e| format!("gpg produced a non-UTF-8 signature: {e}"))
+}
+
+/// Sign `buffer` with `ssh-keygen -Y sign`, the SSH signing-key path.
+/// Unlike `gpg --detach-sign`, `ssh-keygen -Y sign` signs a named file and
+/// writes the signature alongside it as `<file>.sig`, so this round-trips
+/// through temp files rather than stdin/stdout.
+fn sign_with_ssh_keygen(buffer: &str, key: &str) -> Result<String, String> {
+    let msg_path = write_signing_temp_file("commit-sign", buffer)?;
+    let sig_path = PathBuf::from(format!("{}.sig", msg_path.display()));
+
+    let output = std::process::Command::new("ssh-keygen")
+        .args(["-Y", "sign", "-n", "git", "-f", key])
+        .arg(&msg_path)
+        .output();
+
+    let result = match output {
+        Ok(output) if output.status.success() => std::fs::read_to_string(&sig_path)
+            .map_err(|e| format!("Failed to read ssh-keygen signature: {e}")),
+        Ok(output) => Err(format!(
+            "ssh-keygen -Y sign failed: {}",
+            String::from_utf8_lossy(&output.stderr)
+        )),
+        Err(e) => Err(format!("Failed to run ssh-keygen: {e}")),
+    };
+
+    let _ = std::fs::remove_file(&msg_path);
+    let _ = std::fs::remove_file(&sig_path);
+    result
+}
+
+/// Move `repo`'s `HEAD` branch ref to `commit_oid`, the way `Repository::commit`
+/// does internally. Needed because `Repository::commit_signed` writes the
+/// commit object but (unlike `Repository::commit`) has no `update_ref`
+/// parameter to move the branch for us.
+fn update_head_to(repo: &Repository, commit_oid: Oid, message: &str) -> Result<(), git2::Error> {
+    let head_ref_name = repo
+        .find_reference("HEAD")?
+        .symbolic_target()
+        .map(str::to_string)
+        .unwrap_or_else(|| "refs/heads/main".to_string());
+    repo.reference(&head_ref_name, commit_oid, true, message)?;
+    Ok(())
+}
+
+/// Verify a detached `signature` over `signed_data` using `signing`'s
+/// configured format, returning the verifier's human-readable output on
+/// success (which callers may surface for debugging) or as the `Err` string
+/// on failure. `principal` (the commit author's email) is checked against
+/// `signing.allowed_signers_file` when verifying an SSH signature.
+fn verify_signature(
+    signature: &str,
+    signed_data: &str,
+    signing: &SigningConfig,
+    principal: &str,
+) -> Result<String, String> {
+    match signing.format.as_str() {
+        "ssh" => verify_with_ssh_keygen(
+            signature,
+            signed_data,
+            signing.allowed_signers_file.as_deref(),
+            principal,
+        ),
+        _ => verify_with_gpg(signature, signed_data),
+    }
+}
+
+/// Verify with `gpg --verify`, the OpenPGP path. `gpg` reports verification
+/// details (signer, key id, trust) on stderr regardless of outcome.
+fn verify_with_gpg(signature: &str, signed_data: &str) -> Result<String, String> {
+    let sig_path = write_signing_temp_file("verify-sig", signature)?;
+    let data_path = write_signing_temp_file("verify-data", signed_data)?;
+
+    let output = std::process::Command::new("gpg")
+        .arg("--verify")
+        .arg(&sig_path)
+        .arg(&data_path)
+        .output();
+
+    let _ = std::fs::remove_file(&sig_path);
+    let _ = std::fs::remove_file(&data_path);
+
+    let output = output.map_err(|e| format!("Failed to run gpg --verify: {e}"))?;
+    let details = String::from_utf8_lossy(&output.stderr).to_string();
+    if output.status.success() {
+        Ok(details)
+    } else {
+        Err(details)
+    }
+}
+
+/// Verify an SSH signature. When `allowed_signers_file` is configured, this
+/// shells out to `ssh-keygen -Y verify`, which checks the signature against
+/// `principal` (the commit author's email) in that trust file -- the same
+/// validation `git verify-commit` performs for SSH signatures. Without one
+/// configured, this falls back to `ssh-keygen -Y check-novalidate`, which
+/// only confirms the signature is well-formed and matches the key embedded
+/// in it, without validating that key belongs to anyone in particular.
+fn verify_with_ssh_keygen(
+    signature: &str,
+    signed_data: &str,
+    allowed_signers_file: Option<&str>,
+    principal: &str,
+) -> Result<String, String> {
+    let sig_path = write_signing_temp_file("verify-sig", signature)?;
+    let data_path = write_signing_temp_file("verify-data", signed_data)?;
+
+    let data_file =
+        std::fs::File::open(&data_path).map_err(|e| format!("Failed to open signed data: {e}"));
+
+    let output = data_file.and_then(|data_file| {
+        let mut command = std::process::Command::new("ssh-keygen");
+        match allowed_signers_file {
+            Some(allowed_signers_file) => {
+                command.args(["-Y", "verify", "-f", allowed_signers_file, "-I", principal]);
+            }
+            None => {
+                command.args(["-Y", "check-novalidate"]);
+            }
+        }
+        command
+            .args(["-n", "git", "-s"])
+            .arg(&sig_path)
+            .stdin(data_file)
+            .output()
+            .map_err(|e| format!("Failed to run ssh-keygen: {e}"))
+    });
+
+    let _ = std::fs::remove_file(&sig_path);
+    let _ = std::fs::remove_file(&data_path);
+
+    let output = output?;
+    let details = String::from_utf8_lossy(&output.stderr).to_string();
+    if output.status.success() {
+        Ok(details)
+    } else {
+        Err(details)
+    }
+}
+
+/// Whether a commit's signature is present and, if present, cryptographically valid.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct CommitSignatureStatus {
+    /// Whether the commit has a `gpgsig` signature attached at all.
+    pub signed: bool,
+    /// Whether the attached signature verified successfully. Always `false`
+    /// when `signed` is `false`.
+    pub valid: bool,
+    /// Verifier output: verification details on success, the failure reason
+    /// on failure, `None` if the commit isn't signed at all.
+    pub details: Option<String>,
+    /// Identity the signature validated against, when one could be
+    /// determined: the commit author's email for an SSH signature checked
+    /// against `gpg.ssh.allowedSignersFile`, or the signer `gpg --verify`
+    /// reports. `None` when unsigned, invalid, or (SSH with no
+    /// allowed-signers file configured) validated without an identity check.
+    pub signer: Option<String>,
+}
+
+/// Best-effort signer identity for a signature that verified successfully.
+/// For SSH, this is `principal` (the commit author's email) when
+/// `signing.allowed_signers_file` was actually consulted -- `verify_signature`
+/// falls back to `check-novalidate` without one, which confirms nothing about
+/// identity. For OpenPGP, this is the name/email `gpg --verify` reports in
+/// its `Good signature from "..."` line.
+fn signer_identity(signing: &SigningConfig, details: &str, principal: &str) -> Option<String> {
+    if signing.format == "ssh" {
+        return signing
+            .allowed_signers_file
+            .as_ref()
+            .map(|_| principal.to_string())
+            .filter(|principal| !principal.is_empty());
+    }
+
+    details
+        .lines()
+        .find_map(|line| line.split_once("Good signature from \""))
+        .and_then(|(_, rest)| rest.split_once('"'))
+        .map(|(signer, _)| signer.to_string())
+}
+
+/// Extract and verify a commit's `gpgsig` signature, reporting whether one
+/// is present and, if so, whether it verifies against the repo's configured
+/// signing format (`gpg.format`: `"openpgp"` or `"ssh"`) and, for SSH,
+/// `gpg.ssh.allowedSignersFile`.
+#[tauri::command]
+#[specta::specta]
+pub fn git_verify_commit(
+    repo_path: PathBuf,
+    commit_id: String,
+) -> Result<CommitSignatureStatus, String> {
+    let repo = Repository::open(&repo_path).map_err(|e| {
+        log::error!("Failed to open Git repository at {repo_path:?}: {e}");
+        format!("Failed to open Git repository: {e}")
+    })?;
+
+    let oid = Oid::from_str(&commit_id).map_err(|e| format!("Invalid commit id: {e}"))?;
+
+    let signature_result = repo.extract_signature(Some(&oid), Some("gpgsig"));
+    let (signature_buf, signed_data_buf) = match signature_result {
+        Ok(bufs) => bufs,
+        Err(_) => {
+            return Ok(CommitSignatureStatus {
+                signed: false,
+                valid: false,
+                details: None,
+                signer: None,
+            });
+        }
+    };
+
+    let signature = signature_buf.as_str().unwrap_or_default();
+    let signed_data = signed_data_buf.as_str().unwrap_or_default();
+
+    let principal = repo
+        .find_commit(oid)
+        .ok()
+        .and_then(|commit| commit.author().email().map(str::to_string))
+        .unwrap_or_default();
+
+    let signing = signing_config(&repo);
+    match verify_signature(signature, signed_data, &signing, &principal) {
+        Ok(details) => {
+            let signer = signer_identity(&signing, &details, &principal);
+            Ok(CommitSignatureStatus { signed: true, valid: true, details: Some(details), signer })
+        }
+        Err(details) => Ok(CommitSignatureStatus {
+            signed: true,
+            valid: false,
+            details: Some(details),
+            signer: None,
+        }),
+    }
+}
 
 /// Auto-commit files to Git repository with proper LFS handling.
 ///
 /// LFS tracking is automatic via .gitattributes rules created during project init.
 /// Files matching .gitattributes patterns (PDF, Excel >10MB) are automatically tracked by Git LFS.
 ///
+/// Builds the commit tree from scratch (starting at the current `HEAD` tree)
+/// via `git2::TreeBuilder` rather than the repository's index, so this
+/// produces a commit containing only `files` regardless of anything else
+/// staged or modified in the user's working tree.
+///
+/// If `commit.gpgsign` is set in repo config, the commit is detach-signed
+/// with `user.signingkey` (OpenPGP via `gpg`, or SSH via `ssh-keygen -Y sign`
+/// when `gpg.format` is `"ssh"`) before `HEAD` is updated; verify it with
+/// [`git_verify_commit`].
+///
+/// If `amend` is set and `HEAD`'s own commit message starts with the
+/// `[unheard]` prefix and touches exactly `files`, the commit is rewritten in
+/// place (reusing the original author and parents, same as `Commit::amend`)
+/// instead of creating a new one -- this keeps rapid re-saves of the same
+/// file from flooding the log. The rewritten commit goes through the same
+/// signing path as a regular commit, so amending a save on a signing-enabled
+/// repo still produces a signed commit. Amending is skipped if `HEAD` has
+/// already been pushed to its upstream, to avoid rewriting shared history; a
+/// regular commit is created instead.
+///
 /// # Arguments
 /// * `repo_path` - Path to the Git repository
 /// * `files` - List of file paths relative to repo root (e.g., "context/file.csv")
 /// * `message` - Commit message
+/// * `run_hooks` - Whether to run `pre-commit`/`commit-msg`/`post-commit` from
+///   `core.hooksPath` (or `<gitdir>/hooks`); set `false` to bypass them
+///   entirely for fully automated flows
+/// * `amend` - Whether to coalesce into `HEAD` when it looks like the same
+///   logical change (see above) instead of always creating a new commit
 ///
 /// # Returns
 /// The commit ID (SHA) as a string
@@ -24,6 +868,8 @@ pub fn git_auto_commit(
     repo_path: PathBuf,
     files: Vec<String>,
     message: String,
+    run_hooks: bool,
+    amend: bool,
 ) -> Result<String, String> {
     log::info!("Auto-committing {} files to {repo_path:?}", files.len());
 
@@ -42,271 +888,1678 @@ pub fn git_auto_commit(
         format!("Failed to open Git repository: {e}")
     })?;
 
-    // Get index
-    let mut index = repo.index().map_err(|e| {
-        log::error!("Failed to get repository index: {e}");
-        format!("Failed to get repository index: {e}")
-    })?;
+    let workdir = repo.workdir().unwrap_or(&repo_path).to_path_buf();
+    let hooks_dir = hooks_dir(&repo);
+
+    if run_hooks {
+        run_hook(&hooks_dir, "pre-commit", &workdir, &[]).map_err(|output| {
+            log::error!("pre-commit hook rejected the commit: {output}");
+            format!("pre-commit hook rejected the commit: {output}")
+        })?;
+    }
 
-    // Add each file to the index
+    let message = if run_hooks {
+        run_commit_msg_hook(&hooks_dir, &workdir, &message)?
+    } else {
+        message
+    };
+
+    let mut blobs = Vec::with_capacity(files.len());
     for file in &files {
-        let file_path = std::path::Path::new(file);
-        log::debug!("Adding file to index: {file_path:?}");
+        let abs_path = workdir.join(file);
+        let content = std::fs::read(&abs_path).map_err(|e| {
+            log::error!("Failed to read {abs_path:?}: {e}");
+            format!("Failed to read {file}: {e}")
+        })?;
+        blobs.push((file.clone(), content));
+    }
+
+    let base_tree = repo.head().ok().and_then(|head| head.peel_to_tree().ok());
+
+    let tree_id = write_isolated_tree(&repo, base_tree, &blobs).map_err(|e| {
+        log::error!("Failed to build commit tree: {e}");
+        format!("Failed to build commit tree: {e}")
+    })?;
+
+    let tree = repo.find_tree(tree_id).map_err(|e| {
+        log::error!("Failed to find tree: {e}");
+        format!("Failed to find tree: {e}")
+    })?;
+
+    // Get signature (respects Git config, fallback to default)
+    let signature = signature_allow_undefined_name(&repo).map_err(|e| {
+        log::error!("Failed to create signature: {e}");
+        format!("Failed to create signature: {e}")
+    })?;
+
+    // Get parent commit (if exists)
+    let parent_commit = repo
+        .head()
+        .ok()
+        .and_then(|head| head.target().and_then(|oid| repo.find_commit(oid).ok()));
+    let parents: Vec<&Commit> = match &parent_commit {
+        Some(parent) => vec![parent],
+        None => vec![],
+    };
+
+    let should_amend = amend
+        && parent_commit.as_ref().is_some_and(|commit| {
+            commit.message().is_some_and(|msg| msg.starts_with("[unheard]"))
+                && commit_touches_exactly(&repo, commit, &files)
+        })
+        && !head_commit_is_pushed(&repo);
+
+    let signing = signing_config(&repo);
+
+    // Create commit
+    let commit_id = if should_amend {
+        let parent = parent_commit.as_ref().expect("should_amend implies a HEAD commit");
+        // Keep the original author (as `git commit --amend` does), reparent
+        // onto the amended commit's own parents, and run it through the same
+        // commit_create_buffer/sign_commit_buffer/commit_signed path as the
+        // non-amend case below so signed repos don't end up with an unsigned
+        // amended commit.
+        let author = parent.author();
+        let grandparents: Vec<Commit> = parent.parents().collect();
+        let grandparent_refs: Vec<&Commit> = grandparents.iter().collect();
+
+        if signing.enabled {
+            let buffer = repo
+                .commit_create_buffer(&author, &signature, &message, &tree, &grandparent_refs)
+                .map_err(|e| {
+                    log::error!("Failed to build amended commit buffer: {e}");
+                    format!("Failed to build amended commit buffer: {e}")
+                })?;
+            let buffer_str = buffer
+                .as_str()
+                .ok_or_else(|| "Commit buffer is not valid UTF-8".to_string())?;
+
+            let signature_armor = sign_commit_buffer(buffer_str, &signing)?;
+
+            let oid = repo
+                .commit_signed(buffer_str, &signature_armor, Some("gpgsig"))
+                .map_err(|e| {
+                    log::error!("Failed to write signed amended commit: {e}");
+                    format!("Failed to write signed amended commit: {e}")
+                })?;
+
+            update_head_to(&repo, oid, &message).map_err(|e| {
+                log::error!("Failed to update HEAD to signed amended commit: {e}");
+                format!("Failed to update HEAD to signed amended commit: {e}")
+            })?;
+
+            oid
+        } else {
+            repo.commit(Some("HEAD"), &author, &signature, &message, &tree, &grandparent_refs)
+                .map_err(|e| {
+                    log::error!("Failed to amend commit: {e}");
+                    format!("Failed to amend commit: {e}")
+                })?
+        }
+    } else if signing.enabled {
+        let buffer = repo
+            .commit_create_buffer(&signature, &signature, &message, &tree, &parents)
+            .map_err(|e| {
+                log::error!("Failed to build commit buffer: {e}");
+                format!("Failed to build commit buffer: {e}")
+            })?;
+        let buffer_str = buffer
+            .as_str()
+            .ok_or_else(|| "Commit buffer is not valid UTF-8".to_string())?;
+
+        let signature_armor = sign_commit_buffer(buffer_str, &signing)?;
+
+        let oid = repo
+            .commit_signed(buffer_str, &signature_armor, Some("gpgsig"))
+            .map_err(|e| {
+                log::error!("Failed to write signed commit: {e}");
+                format!("Failed to write signed commit: {e}")
+            })?;
+
+        update_head_to(&repo, oid, &message).map_err(|e| {
+            log::error!("Failed to update HEAD to signed commit: {e}");
+            format!("Failed to update HEAD to signed commit: {e}")
+        })?;
+
+        oid
+    } else {
+        repo.commit(Some("HEAD"), &signature, &signature, &message, &tree, &parents)
+            .map_err(|e| {
+                log::error!("Failed to create commit: {e}");
+                format!("Failed to create commit: {e}")
+            })?
+    };
+
+    let commit_hash = commit_id.to_string();
+    log::info!("Created commit: {commit_hash}");
+
+    if run_hooks {
+        if let Err(output) = run_hook(&hooks_dir, "post-commit", &workdir, &[]) {
+            // The commit already succeeded; post-commit is advisory only.
+            log::warn!("post-commit hook failed: {output}");
+        }
+    }
+
+    Ok(commit_hash)
+}
+
+// ============================================================================
+// Working-tree status and sync
+// ============================================================================
+
+/// Structured status of a candidate project path, checked before a save
+/// command (context upload, decision log, Attio import) writes to it.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectRepoStatus {
+    /// Whether `project_path` is (or is inside) a Git repository, bare or not.
+    pub is_repo: bool,
+    /// Whether the repository is bare, and so has no worktree for save
+    /// commands to write context files/decision logs/Attio imports into.
+    pub is_bare: bool,
+    /// Whether `HEAD` is unborn, i.e. the repository has no commits yet.
+    pub is_unborn: bool,
+    /// Whether the worktree has uncommitted or untracked changes. Always
+    /// `false` when the repo is bare, since there's no worktree to diff.
+    pub is_dirty: bool,
+}
+
+/// Pre-flight check for `project_path` before a save command writes to it.
+/// Opens with `Repository::open_ext` and no flags, which accepts a bare
+/// repository and also searches parent directories for one, letting
+/// bareness be reported as a
+/// structured status alongside an unborn `HEAD` and a dirty worktree -- the
+/// frontend can then show an actionable message ("this folder isn't a Git
+/// repo" / "bare repos aren't supported") before any files are written,
+/// instead of discovering it only after `git_auto_commit` fails partway
+/// through a save.
+#[tauri::command]
+#[specta::specta]
+pub fn check_project_repo(project_path: PathBuf) -> Result<ProjectRepoStatus, String> {
+    log::info!("Checking repository status for {project_path:?}");
+
+    let repo = match Repository::open_ext(
+        &project_path,
+        RepositoryOpenFlags::empty(),
+        std::iter::empty::<&std::ffi::OsStr>(),
+    ) {
+        Ok(repo) => repo,
+        Err(_) => {
+            return Ok(ProjectRepoStatus {
+                is_repo: false,
+                is_bare: false,
+                is_unborn: false,
+                is_dirty: false,
+            });
+        }
+    };
+
+    let is_bare = repo.is_bare();
+    let is_unborn = matches!(repo.head(), Err(e) if e.code() == git2::ErrorCode::UnbornBranch);
+
+    let is_dirty = if is_bare {
+        false
+    } else {
+        let mut opts = StatusOptions::new();
+        opts.include_untracked(true).recurse_untracked_dirs(true);
+        repo.statuses(Some(&mut opts))
+            .map(|statuses| !statuses.is_empty())
+            .unwrap_or(false)
+    };
+
+    Ok(ProjectRepoStatus { is_repo: true, is_bare, is_unborn, is_dirty })
+}
+
+/// Get the repository's working-tree status: how many paths have
+/// uncommitted changes, and whether `HEAD` is in sync with its upstream.
+#[tauri::command]
+#[specta::specta]
+pub fn get_git_status(project_path: PathBuf) -> Result<crate::types::GitStatus, String> {
+    log::info!("Getting Git status for {project_path:?}");
+
+    let repo = Repository::open(&project_path).map_err(|e| {
+        log::error!("Failed to open Git repository: {e}");
+        format!("Failed to open Git repository: {e}")
+    })?;
+
+    let mut opts = StatusOptions::new();
+    opts.include_untracked(true).recurse_untracked_dirs(true);
+
+    let statuses = repo
+        .statuses(Some(&mut opts))
+        .map_err(|e| format!("Failed to read Git status: {e}"))?;
+
+    let synced = branch_synced_with_upstream(&repo).unwrap_or(true);
+
+    Ok(crate::types::GitStatus {
+        uncommitted_changes: statuses.len(),
+        synced,
+    })
+}
+
+/// `HEAD`'s ahead/behind commit counts relative to its configured upstream
+/// tracking branch, or `None` if `HEAD` isn't a branch or has no upstream
+/// configured.
+fn ahead_behind_of_upstream(repo: &Repository) -> Result<Option<(usize, usize)>, git2::Error> {
+    let head = repo.head()?;
+    let local_oid = head.target().ok_or_else(|| git2::Error::from_str("HEAD has no target"))?;
+
+    let branch_name = head
+        .shorthand()
+        .ok_or_else(|| git2::Error::from_str("HEAD is not a branch"))?;
+
+    let upstream_name = match repo.branch_upstream_name(&format!("refs/heads/{branch_name}")) {
+        Ok(name) => name,
+        Err(_) => return Ok(None),
+    };
+    let upstream_name = upstream_name
+        .as_str()
+        .ok_or_else(|| git2::Error::from_str("Upstream ref name is not valid UTF-8"))?;
+
+    let upstream_oid = repo
+        .find_reference(upstream_name)?
+        .target()
+        .ok_or_else(|| git2::Error::from_str("Upstream ref has no target"))?;
+
+    Ok(Some(repo.graph_ahead_behind(local_oid, upstream_oid)?))
+}
+
+/// Compare `HEAD` against its configured upstream tracking branch.
+/// Returns `true` when the branch has no upstream configured (nothing to
+/// be out of sync with) as well as when it's even with its upstream.
+fn branch_synced_with_upstream(repo: &Repository) -> Result<bool, git2::Error> {
+    match ahead_behind_of_upstream(repo)? {
+        Some((ahead, behind)) => Ok(ahead == 0 && behind == 0),
+        None => Ok(true),
+    }
+}
+
+/// Whether `HEAD`'s current commit has already been pushed: there's an
+/// upstream configured and local `HEAD` isn't ahead of it. Unlike
+/// `branch_synced_with_upstream`, a branch with no upstream at all reports
+/// `false` here, since there's nothing shared to protect by refusing to
+/// rewrite it.
+fn head_commit_is_pushed(repo: &Repository) -> bool {
+    matches!(ahead_behind_of_upstream(repo), Ok(Some((0, _))))
+}
+
+/// Stage every change in the working tree and commit on top of the current
+/// `HEAD` (unlike `initialize_git`, this never assumes an empty parent
+/// list, since the project may already have history).
+#[tauri::command]
+#[specta::specta]
+pub fn commit_changes(project_path: PathBuf, message: String) -> Result<String, String> {
+    log::info!("Committing working-tree changes in {project_path:?}");
+
+    if message.trim().is_empty() {
+        return Err("Commit message cannot be empty".to_string());
+    }
+
+    let repo = Repository::open(&project_path).map_err(|e| {
+        log::error!("Failed to open Git repository: {e}");
+        format!("Failed to open Git repository: {e}")
+    })?;
+
+    let mut index = repo
+        .index()
+        .map_err(|e| format!("Failed to get repository index: {e}"))?;
+
+    index
+        .add_all(["."], git2::IndexAddOption::DEFAULT, None)
+        .map_err(|e| format!("Failed to stage changes: {e}"))?;
+    index
+        .write()
+        .map_err(|e| format!("Failed to write index: {e}"))?;
+
+    let tree_id = index
+        .write_tree()
+        .map_err(|e| format!("Failed to write tree: {e}"))?;
+    let tree = repo
+        .find_tree(tree_id)
+        .map_err(|e| format!("Failed to find tree: {e}"))?;
+
+    let (signature, _source) = resolve_init_signature(&repo);
+
+    let parent_commit = repo.head().ok().and_then(|head| head.peel_to_commit().ok());
+
+    let commit_id = match parent_commit {
+        Some(parent) => repo.commit(
+            Some("HEAD"),
+            &signature,
+            &signature,
+            &message,
+            &tree,
+            &[&parent],
+        ),
+        None => repo.commit(Some("HEAD"), &signature, &signature, &message, &tree, &[]),
+    }
+    .map_err(|e| {
+        log::error!("Failed to create commit: {e}");
+        format!("Failed to create commit: {e}")
+    })?;
+
+    let commit_hash = commit_id.to_string();
+    log::info!("Committed working tree: {commit_hash}");
+    Ok(commit_hash)
+}
+
+/// Push `HEAD`'s branch to `remote` (e.g. `"origin"`), authenticating via the
+/// SSH agent when the remote accepts SSH keys, otherwise libgit2's default
+/// credentials.
+#[tauri::command]
+#[specta::specta]
+pub fn push_to_remote(project_path: PathBuf, remote: String) -> Result<(), String> {
+    log::info!("Pushing {project_path:?} to remote '{remote}'");
+
+    let repo = Repository::open(&project_path).map_err(|e| {
+        log::error!("Failed to open Git repository: {e}");
+        format!("Failed to open Git repository: {e}")
+    })?;
+
+    let head = repo
+        .head()
+        .map_err(|e| format!("Failed to resolve HEAD: {e}"))?;
+    let branch_name = head
+        .shorthand()
+        .ok_or_else(|| "HEAD is not a branch".to_string())?;
+    let refspec = format!("refs/heads/{branch_name}:refs/heads/{branch_name}");
+
+    let mut remote = repo
+        .find_remote(&remote)
+        .map_err(|e| format!("Failed to find remote '{remote}': {e}"))?;
+
+    let mut callbacks = git2::RemoteCallbacks::new();
+    callbacks.credentials(|_url, username_from_url, allowed_types| {
+        if allowed_types.contains(git2::CredentialType::SSH_KEY) {
+            if let Some(username) = username_from_url {
+                return git2::Cred::ssh_key_from_agent(username);
+            }
+        }
+        git2::Cred::default()
+    });
+
+    let mut push_options = git2::PushOptions::new();
+    push_options.remote_callbacks(callbacks);
+
+    remote
+        .push(&[refspec], Some(&mut push_options))
+        .map_err(|e| {
+            log::error!("Failed to push to remote: {e}");
+            format!("Failed to push to remote: {e}")
+        })?;
+
+    log::info!("Pushed {branch_name} to remote");
+    Ok(())
+}
+
+// ============================================================================
+// Per-file commit history
+// ============================================================================
+
+/// How a file changed in a single commit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub enum FileChangeKind {
+    Added,
+    Modified,
+    Deleted,
+}
+
+/// One revision of a file, as seen walking history from `HEAD`.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct FileHistoryEntry {
+    pub commit_hash: String,
+    pub author: String,
+    /// Commit time, seconds since the Unix epoch.
+    pub timestamp: i64,
+    pub summary: String,
+    pub change_kind: FileChangeKind,
+}
+
+/// Walk commit history from `HEAD`, returning every commit that touched
+/// `relative_path` along with how it changed in that commit.
+#[tauri::command]
+#[specta::specta]
+pub fn get_file_history(
+    project_path: PathBuf,
+    relative_path: String,
+) -> Result<Vec<FileHistoryEntry>, String> {
+    log::info!("Getting file history for {relative_path} in {project_path:?}");
+
+    let repo = Repository::open(&project_path)
+        .map_err(|e| format!("Failed to open Git repository: {e}"))?;
+
+    let mut revwalk = repo
+        .revwalk()
+        .map_err(|e| format!("Failed to create revwalk: {e}"))?;
+    revwalk
+        .push_head()
+        .map_err(|e| format!("Failed to walk from HEAD: {e}"))?;
+
+    let target = Path::new(&relative_path);
+    let mut history = Vec::new();
+
+    for oid in revwalk {
+        let oid = oid.map_err(|e| format!("Failed to walk history: {e}"))?;
+        let commit = repo
+            .find_commit(oid)
+            .map_err(|e| format!("Failed to read commit {oid}: {e}"))?;
+        let tree = commit
+            .tree()
+            .map_err(|e| format!("Failed to read commit tree: {e}"))?;
+        let parent_tree = commit.parent(0).ok().and_then(|p| p.tree().ok());
+
+        let diff = repo
+            .diff_tree_to_tree(parent_tree.as_ref(), Some(&tree), None)
+            .map_err(|e| format!("Failed to diff commit {oid}: {e}"))?;
+
+        let mut change_kind = None;
+        diff.foreach(
+            &mut |delta, _progress| {
+                let path_matches = |p: Option<&Path>| p == Some(target);
+                if path_matches(delta.old_file().path()) || path_matches(delta.new_file().path())
+                {
+                    change_kind = Some(match delta.status() {
+                        git2::Delta::Added => FileChangeKind::Added,
+                        git2::Delta::Deleted => FileChangeKind::Deleted,
+                        _ => FileChangeKind::Modified,
+                    });
+                }
+                true
+            },
+            None,
+            None,
+            None,
+        )
+        .map_err(|e| format!("Failed to inspect diff for commit {oid}: {e}"))?;
+
+        if let Some(change_kind) = change_kind {
+            history.push(FileHistoryEntry {
+                commit_hash: oid.to_string(),
+                author: commit.author().name().unwrap_or("unknown").to_string(),
+                timestamp: commit.time().seconds(),
+                summary: commit.summary().unwrap_or_default().to_string(),
+                change_kind,
+            });
+        }
+    }
+
+    log::info!("Found {} revision(s) of {relative_path}", history.len());
+    Ok(history)
+}
+
+/// Retrieve the bytes of `relative_path` as it existed at `commit_hash`, so
+/// the frontend can preview or restore an earlier version of a file.
+#[tauri::command]
+#[specta::specta]
+pub fn get_file_at_commit(
+    project_path: PathBuf,
+    relative_path: String,
+    commit_hash: String,
+) -> Result<Vec<u8>, String> {
+    log::info!("Reading {relative_path} at commit {commit_hash} in {project_path:?}");
+
+    let repo = Repository::open(&project_path)
+        .map_err(|e| format!("Failed to open Git repository: {e}"))?;
+
+    let oid = git2::Oid::from_str(&commit_hash)
+        .map_err(|e| format!("Invalid commit hash: {e}"))?;
+    let commit = repo
+        .find_commit(oid)
+        .map_err(|e| format!("Failed to find commit {commit_hash}: {e}"))?;
+    let tree = commit
+        .tree()
+        .map_err(|e| format!("Failed to read commit tree: {e}"))?;
+
+    let entry = tree
+        .get_path(Path::new(&relative_path))
+        .map_err(|e| format!("{relative_path} not found at commit {commit_hash}: {e}"))?;
+    let object = entry
+        .to_object(&repo)
+        .map_err(|e| format!("Failed to load blob: {e}"))?;
+    let blob = object
+        .as_blob()
+        .ok_or_else(|| format!("{relative_path} is not a file at commit {commit_hash}"))?;
+
+    Ok(blob.content().to_vec())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    /// Create a test repository with .gitattributes
+    fn create_test_repo() -> (TempDir, PathBuf) {
+        let temp_dir = TempDir::new().unwrap();
+        let repo_path = temp_dir.path().to_path_buf();
+
+        // Initialize repo
+        let repo = Repository::init(&repo_path).unwrap();
+
+        // Create context directory
+        fs::create_dir_all(repo_path.join("context")).unwrap();
+
+        // Create .gitattributes with LFS rules
+        let gitattributes_content = r#"# Git LFS tracking for large files
+context/**/*.pdf filter=lfs diff=lfs merge=lfs -text
+context/**/*.xlsx filter=lfs diff=lfs merge=lfs -text
+"#;
+        fs::write(repo_path.join(".gitattributes"), gitattributes_content).unwrap();
+
+        // Initial commit
+        let mut index = repo.index().unwrap();
+        index
+            .add_all(["."], git2::IndexAddOption::DEFAULT, None)
+            .unwrap();
+        index.write().unwrap();
+
+        let tree_id = index.write_tree().unwrap();
+        let tree = repo.find_tree(tree_id).unwrap();
+        let signature = Signature::now("Test User", "test@example.com").unwrap();
+
+        repo.commit(
+            Some("HEAD"),
+            &signature,
+            &signature,
+            "Initial commit",
+            &tree,
+            &[],
+        )
+        .unwrap();
+
+        (temp_dir, repo_path)
+    }
+
+    #[test]
+    fn test_git_auto_commit_single_file() {
+        let (_temp_dir, repo_path) = create_test_repo();
+
+        // Create a CSV file
+        let csv_path = repo_path.join("context/test.csv");
+        fs::write(&csv_path, "name,age\nAlice,30\nBob,25").unwrap();
+
+        // Commit the file
+        let result = git_auto_commit(
+            repo_path.clone(),
+            vec!["context/test.csv".to_string()],
+            "Add context: test.csv".to_string(),
+            true,
+            false,
+        );
+
+        assert!(result.is_ok());
+        let commit_hash = result.unwrap();
+        assert!(!commit_hash.is_empty());
+        assert_eq!(commit_hash.len(), 40); // Git SHA is 40 characters
+
+        // Verify commit was created
+        let repo = Repository::open(repo_path).unwrap();
+        let head = repo.head().unwrap();
+        let commit = head.peel_to_commit().unwrap();
+        assert_eq!(commit.message().unwrap(), "Add context: test.csv");
+    }
+
+    #[test]
+    fn test_git_auto_commit_multiple_files() {
+        let (_temp_dir, repo_path) = create_test_repo();
+
+        // Create multiple files
+        fs::write(repo_path.join("context/file1.csv"), "name,age\nAlice,30").unwrap();
+        fs::write(repo_path.join("context/file2.csv"), "name,score\nBob,95").unwrap();
+
+        // Commit multiple files
+        let result = git_auto_commit(
+            repo_path.clone(),
+            vec![
+                "context/file1.csv".to_string(),
+                "context/file2.csv".to_string(),
+            ],
+            "Add context files: file1.csv, file2.csv".to_string(),
+            true,
+            false,
+        );
+
+        assert!(result.is_ok());
+
+        // Verify commit message
+        let repo = Repository::open(repo_path).unwrap();
+        let head = repo.head().unwrap();
+        let commit = head.peel_to_commit().unwrap();
+        assert_eq!(
+            commit.message().unwrap(),
+            "Add context files: file1.csv, file2.csv"
+        );
+    }
+
+    #[test]
+    fn test_git_auto_commit_empty_files() {
+        let (_temp_dir, repo_path) = create_test_repo();
+
+        // Try to commit with empty file list
+        let result = git_auto_commit(repo_path, vec![], "Empty commit".to_string(), true, false);
+
+        assert!(result.is_err());
+        assert!(result.unwrap_err().contains("No files provided"));
+    }
+
+    #[test]
+    fn test_git_auto_commit_empty_message() {
+        let (_temp_dir, repo_path) = create_test_repo();
+
+        // Create a file
+        fs::write(repo_path.join("context/test.csv"), "data").unwrap();
+
+        // Try to commit with empty message
+        let result = git_auto_commit(
+            repo_path,
+            vec!["context/test.csv".to_string()],
+            "   ".to_string(),
+            true,
+            false,
+        );
+
+        assert!(result.is_err());
+        assert!(result
+            .unwrap_err()
+            .contains("Commit message cannot be empty"));
+    }
+
+    #[test]
+    fn test_git_auto_commit_missing_repo() {
+        let temp_dir = TempDir::new().unwrap();
+        let non_repo_path = temp_dir.path().to_path_buf();
+
+        let result = git_auto_commit(
+            non_repo_path,
+            vec!["test.csv".to_string()],
+            "Test commit".to_string(),
+            true,
+            false,
+        );
+
+        assert!(result.is_err());
+        assert!(result
+            .unwrap_err()
+            .contains("Failed to open Git repository"));
+    }
+
+    #[test]
+    fn test_git_auto_commit_ignores_unrelated_staged_changes() {
+        let (_temp_dir, repo_path) = create_test_repo();
+
+        // Create and stage a file that should NOT end up in the commit.
+        fs::write(repo_path.join("context/unrelated.csv"), "x,y\n1,2").unwrap();
+        let repo = Repository::open(&repo_path).unwrap();
+        let mut index = repo.index().unwrap();
+        index
+            .add_path(Path::new("context/unrelated.csv"))
+            .unwrap();
+        index.write().unwrap();
+
+        // Create the file we actually want committed.
+        fs::write(repo_path.join("context/test.csv"), "data").unwrap();
+
+        let result = git_auto_commit(
+            repo_path.clone(),
+            vec!["context/test.csv".to_string()],
+            "Test commit".to_string(),
+            true,
+            false,
+        );
+        assert!(result.is_ok());
+
+        let repo = Repository::open(&repo_path).unwrap();
+        let tree = repo.head().unwrap().peel_to_tree().unwrap();
+        assert!(tree.get_path(Path::new("context/test.csv")).is_ok());
+        assert!(tree.get_path(Path::new("context/unrelated.csv")).is_err());
+    }
+
+    #[test]
+    fn test_git_auto_commit_preserves_unrelated_head_files() {
+        let (_temp_dir, repo_path) = create_test_repo();
+
+        fs::write(repo_path.join("context/test.csv"), "data").unwrap();
+        git_auto_commit(
+            repo_path.clone(),
+            vec!["context/test.csv".to_string()],
+            "Test commit".to_string(),
+            true,
+            false,
+        )
+        .unwrap();
+
+        // .gitattributes was part of the initial commit, not this one; it
+        // should still be present in the resulting tree.
+        let repo = Repository::open(&repo_path).unwrap();
+        let tree = repo.head().unwrap().peel_to_tree().unwrap();
+        assert!(tree.get_path(Path::new(".gitattributes")).is_ok());
+        assert!(tree.get_path(Path::new("context/test.csv")).is_ok());
+    }
+
+    #[test]
+    fn test_git_auto_commit_nested_path() {
+        let (_temp_dir, repo_path) = create_test_repo();
+
+        fs::create_dir_all(repo_path.join("context/nested")).unwrap();
+        fs::write(repo_path.join("context/nested/deep.csv"), "a,b").unwrap();
+
+        let result = git_auto_commit(
+            repo_path.clone(),
+            vec!["context/nested/deep.csv".to_string()],
+            "Add nested file".to_string(),
+            true,
+            false,
+        );
+        assert!(result.is_ok());
+
+        let repo = Repository::open(&repo_path).unwrap();
+        let tree = repo.head().unwrap().peel_to_tree().unwrap();
+        assert!(tree.get_path(Path::new("context/nested/deep.csv")).is_ok());
+    }
+
+    #[cfg(unix)]
+    fn write_executable_hook(hooks_dir: &Path, name: &str, script: &str) {
+        use std::os::unix::fs::PermissionsExt;
+        fs::create_dir_all(hooks_dir).unwrap();
+        let path = hooks_dir.join(name);
+        fs::write(&path, script).unwrap();
+        let mut perms = fs::metadata(&path).unwrap().permissions();
+        perms.set_mode(0o755);
+        fs::set_permissions(&path, perms).unwrap();
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_git_auto_commit_aborts_on_failing_pre_commit_hook() {
+        let (_temp_dir, repo_path) = create_test_repo();
+        write_executable_hook(
+            &repo_path.join(".git/hooks"),
+            "pre-commit",
+            "#!/bin/sh\necho nope >&2\nexit 1\n",
+        );
+
+        fs::write(repo_path.join("context/test.csv"), "data").unwrap();
+        let result = git_auto_commit(
+            repo_path,
+            vec!["context/test.csv".to_string()],
+            "Test commit".to_string(),
+            true,
+            false,
+        );
+
+        assert!(result.is_err());
+        assert!(result.unwrap_err().contains("nope"));
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_git_auto_commit_run_hooks_false_bypasses_failing_pre_commit() {
+        let (_temp_dir, repo_path) = create_test_repo();
+        write_executable_hook(
+            &repo_path.join(".git/hooks"),
+            "pre-commit",
+            "#!/bin/sh\nexit 1\n",
+        );
+
+        fs::write(repo_path.join("context/test.csv"), "data").unwrap();
+        let result = git_auto_commit(
+            repo_path,
+            vec!["context/test.csv".to_string()],
+            "Test commit".to_string(),
+            false,
+            false,
+        );
+
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_git_auto_commit_applies_commit_msg_hook_rewrite() {
+        let (_temp_dir, repo_path) = create_test_repo();
+        write_executable_hook(
+            &repo_path.join(".git/hooks"),
+            "commit-msg",
+            "#!/bin/sh\necho 'rewritten message' > \"$1\"\n",
+        );
+
+        fs::write(repo_path.join("context/test.csv"), "data").unwrap();
+        git_auto_commit(
+            repo_path.clone(),
+            vec!["context/test.csv".to_string()],
+            "Original message".to_string(),
+            true,
+            false,
+        )
+        .unwrap();
+
+        let repo = Repository::open(&repo_path).unwrap();
+        let commit = repo.head().unwrap().peel_to_commit().unwrap();
+        assert_eq!(commit.message().unwrap(), "rewritten message\n");
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_git_auto_commit_runs_post_commit_hook() {
+        let (_temp_dir, repo_path) = create_test_repo();
+        let marker = repo_path.join("post-commit-ran");
+        write_executable_hook(
+            &repo_path.join(".git/hooks"),
+            "post-commit",
+            &format!("#!/bin/sh\ntouch {}\n", marker.display()),
+        );
+
+        fs::write(repo_path.join("context/test.csv"), "data").unwrap();
+        git_auto_commit(
+            repo_path,
+            vec!["context/test.csv".to_string()],
+            "Test commit".to_string(),
+            true,
+            false,
+        )
+        .unwrap();
+
+        assert!(marker.exists());
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_git_auto_commit_resolves_hooks_path_from_config() {
+        let (_temp_dir, repo_path) = create_test_repo();
+        write_executable_hook(
+            &repo_path.join("custom-hooks"),
+            "pre-commit",
+            "#!/bin/sh\nexit 1\n",
+        );
+
+        let repo = Repository::open(&repo_path).unwrap();
+        repo.config()
+            .unwrap()
+            .set_str("core.hooksPath", "custom-hooks")
+            .unwrap();
+
+        fs::write(repo_path.join("context/test.csv"), "data").unwrap();
+        let result = git_auto_commit(
+            repo_path,
+            vec!["context/test.csv".to_string()],
+            "Test commit".to_string(),
+            true,
+            false,
+        );
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_git_auto_commit_lfs_file() {
+        let (_temp_dir, repo_path) = create_test_repo();
+
+        // Create a PDF file (LFS-tracked via .gitattributes)
+        // Note: Actual LFS tracking requires git-lfs to be installed
+        // This test verifies the commit succeeds regardless of LFS availability
+        let pdf_path = repo_path.join("context/test.pdf");
+        fs::write(&pdf_path, b"%PDF-1.4\nMock PDF content").unwrap();
+
+        let result = git_auto_commit(
+            repo_path,
+            vec!["context/test.pdf".to_string()],
+            "Add context: test.pdf".to_string(),
+            true,
+            false,
+        );
+
+        // Should succeed even if LFS is not installed (Git falls back to normal storage)
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_get_git_status_counts_uncommitted_changes() {
+        let (_temp_dir, repo_path) = create_test_repo();
+        fs::write(repo_path.join("context/a.csv"), "a,b").unwrap();
+        fs::write(repo_path.join("context/b.csv"), "c,d").unwrap();
+
+        let status = get_git_status(repo_path).unwrap();
+        assert_eq!(status.uncommitted_changes, 2);
+    }
+
+    #[test]
+    fn test_get_git_status_no_upstream_is_synced() {
+        let (_temp_dir, repo_path) = create_test_repo();
+
+        let status = get_git_status(repo_path).unwrap();
+        assert!(status.synced);
+    }
+
+    #[test]
+    fn test_check_project_repo_reports_clean_repo() {
+        let (_temp_dir, repo_path) = create_test_repo();
+
+        let status = check_project_repo(repo_path).unwrap();
+        assert!(status.is_repo);
+        assert!(!status.is_bare);
+        assert!(!status.is_unborn);
+        assert!(!status.is_dirty);
+    }
+
+    #[test]
+    fn test_check_project_repo_reports_dirty_worktree() {
+        let (_temp_dir, repo_path) = create_test_repo();
+        fs::write(repo_path.join("context/untracked.csv"), "a,b").unwrap();
+
+        let status = check_project_repo(repo_path).unwrap();
+        assert!(status.is_dirty);
+    }
+
+    #[test]
+    fn test_check_project_repo_reports_not_a_repo() {
+        let temp_dir = TempDir::new().unwrap();
+
+        let status = check_project_repo(temp_dir.path().to_path_buf()).unwrap();
+        assert!(!status.is_repo);
+        assert!(!status.is_bare);
+        assert!(!status.is_unborn);
+        assert!(!status.is_dirty);
+    }
+
+    #[test]
+    fn test_check_project_repo_reports_bare_repo() {
+        let temp_dir = TempDir::new().unwrap();
+        Repository::init_bare(temp_dir.path()).unwrap();
+
+        let status = check_project_repo(temp_dir.path().to_path_buf()).unwrap();
+        assert!(status.is_repo);
+        assert!(status.is_bare);
+        assert!(!status.is_dirty);
+    }
+
+    #[test]
+    fn test_check_project_repo_reports_unborn_head() {
+        let temp_dir = TempDir::new().unwrap();
+        Repository::init(temp_dir.path()).unwrap();
+
+        let status = check_project_repo(temp_dir.path().to_path_buf()).unwrap();
+        assert!(status.is_repo);
+        assert!(status.is_unborn);
+    }
+
+    #[test]
+    fn test_commit_changes_stages_and_commits_working_tree() {
+        let (_temp_dir, repo_path) = create_test_repo();
+        fs::write(repo_path.join("context/a.csv"), "a,b").unwrap();
+
+        let commit_hash = commit_changes(repo_path.clone(), "Save changes".to_string()).unwrap();
+        assert!(!commit_hash.is_empty());
+
+        let repo = Repository::open(&repo_path).unwrap();
+        let head = repo.head().unwrap().peel_to_commit().unwrap();
+        assert_eq!(head.message().unwrap(), "Save changes");
+        assert_eq!(head.parent_count(), 1);
+
+        let status = get_git_status(repo_path).unwrap();
+        assert_eq!(status.uncommitted_changes, 0);
+    }
+
+    #[test]
+    fn test_commit_changes_rejects_empty_message() {
+        let (_temp_dir, repo_path) = create_test_repo();
+
+        let result = commit_changes(repo_path, "   ".to_string());
+        assert!(result.is_err());
+        assert!(result.unwrap_err().contains("Commit message cannot be empty"));
+    }
+
+    #[test]
+    fn test_get_file_history_tracks_add_and_modify() {
+        let (_temp_dir, repo_path) = create_test_repo();
+
+        fs::write(repo_path.join("context/data.csv"), "a,b\n1,2").unwrap();
+        git_auto_commit(
+            repo_path.clone(),
+            vec!["context/data.csv".to_string()],
+            "Add data.csv".to_string(),
+            true,
+            false,
+        )
+        .unwrap();
+
+        fs::write(repo_path.join("context/data.csv"), "a,b\n1,2\n3,4").unwrap();
+        git_auto_commit(
+            repo_path.clone(),
+            vec!["context/data.csv".to_string()],
+            "Update data.csv".to_string(),
+            true,
+            false,
+        )
+        .unwrap();
+
+        let history = get_file_history(repo_path, "context/data.csv".to_string()).unwrap();
+
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[0].summary, "Update data.csv");
+        assert_eq!(history[0].change_kind, FileChangeKind::Modified);
+        assert_eq!(history[1].summary, "Add data.csv");
+        assert_eq!(history[1].change_kind, FileChangeKind::Added);
+    }
+
+    #[test]
+    fn test_get_file_history_ignores_unrelated_commits() {
+        let (_temp_dir, repo_path) = create_test_repo();
+
+        fs::write(repo_path.join("context/other.csv"), "x,y\n1,2").unwrap();
+        git_auto_commit(
+            repo_path.clone(),
+            vec!["context/other.csv".to_string()],
+            "Add other.csv".to_string(),
+            true,
+            false,
+        )
+        .unwrap();
+
+        let history = get_file_history(repo_path, "context/data.csv".to_string()).unwrap();
+        assert!(history.is_empty());
+    }
+
+    #[test]
+    fn test_get_file_at_commit_returns_historical_content() {
+        let (_temp_dir, repo_path) = create_test_repo();
+
+        fs::write(repo_path.join("context/data.csv"), "a,b\n1,2").unwrap();
+        let commit_hash = git_auto_commit(
+            repo_path.clone(),
+            vec!["context/data.csv".to_string()],
+            "Add data.csv".to_string(),
+            true,
+            false,
+        )
+        .unwrap();
+
+        fs::write(repo_path.join("context/data.csv"), "a,b\n9,9").unwrap();
+        git_auto_commit(
+            repo_path.clone(),
+            vec!["context/data.csv".to_string()],
+            "Overwrite data.csv".to_string(),
+            true,
+            false,
+        )
+        .unwrap();
+
+        let bytes = get_file_at_commit(repo_path, "context/data.csv".to_string(), commit_hash)
+            .unwrap();
+        assert_eq!(String::from_utf8(bytes).unwrap(), "a,b\n1,2");
+    }
+
+    #[test]
+    fn test_repo_statuses_detects_untracked_and_modified() {
+        let (_temp_dir, repo_path) = create_test_repo();
+
+        fs::write(repo_path.join("context/untracked.csv"), "a,b\n1,2").unwrap();
+
+        let statuses = repo_statuses(&repo_path).unwrap();
+        let status = statuses
+            .get(Path::new("context/untracked.csv"))
+            .expect("untracked file should be present in status map");
+        assert!(status.is_wt_new());
+    }
+
+    #[test]
+    fn test_validate_email_shape_accepts_valid() {
+        assert!(validate_email_shape("user@unheard.local").is_ok());
+    }
+
+    #[test]
+    fn test_validate_email_shape_rejects_missing_at() {
+        let result = validate_email_shape("not-an-email");
+        assert!(result.is_err());
+        assert!(result.unwrap_err().contains("must contain"));
+    }
+
+    #[test]
+    fn test_validate_email_shape_rejects_missing_domain_dot() {
+        let result = validate_email_shape("user@localhost");
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_resolve_init_signature_uses_repo_config() {
+        let (_temp_dir, repo_path) = create_test_repo();
+        let repo = Repository::open(&repo_path).unwrap();
+
+        let mut config = repo.config().unwrap();
+        config.set_str("user.name", "Repo Author").unwrap();
+        config.set_str("user.email", "author@example.com").unwrap();
+
+        let (signature, source) = resolve_init_signature(&repo);
+        assert_eq!(source, GitIdentitySource::Repo);
+        assert_eq!(signature.name(), Some("Repo Author"));
+        assert_eq!(signature.email(), Some("author@example.com"));
+    }
+
+    #[test]
+    fn test_signature_allow_undefined_name_uses_full_repo_identity() {
+        let (_temp_dir, repo_path) = create_test_repo();
+        let repo = Repository::open(&repo_path).unwrap();
+
+        let mut config = repo.config().unwrap();
+        config.set_str("user.name", "Repo Author").unwrap();
+        config.set_str("user.email", "author@example.com").unwrap();
+
+        let signature = signature_allow_undefined_name(&repo).unwrap();
+        assert_eq!(signature.name(), Some("Repo Author"));
+        assert_eq!(signature.email(), Some("author@example.com"));
+    }
+
+    #[test]
+    fn test_signature_allow_undefined_name_falls_back_to_email_only() {
+        let (_temp_dir, repo_path) = create_test_repo();
+        let repo = Repository::open(&repo_path).unwrap();
+
+        // Only `user.email` configured, no `user.name` -- `repo.signature()`
+        // itself can't resolve a signature from this alone.
+        repo.config()
+            .unwrap()
+            .set_str("user.email", "email-only@example.com")
+            .unwrap();
+        assert!(repo.signature().is_err());
+
+        let signature = signature_allow_undefined_name(&repo).unwrap();
+        assert_eq!(signature.name(), Some("unknown"));
+        assert_eq!(signature.email(), Some("email-only@example.com"));
+    }
+
+    #[test]
+    fn test_signature_allow_undefined_name_falls_back_to_app_default() {
+        let (_temp_dir, repo_path) = create_test_repo();
+        let repo = Repository::open(&repo_path).unwrap();
+
+        let signature = signature_allow_undefined_name(&repo).unwrap();
+        assert_eq!(signature.name(), Some("Unheard User"));
+        assert_eq!(signature.email(), Some("user@unheard.local"));
+    }
+
+    #[test]
+    fn test_git_auto_commit_attributes_email_only_identity() {
+        let (_temp_dir, repo_path) = create_test_repo();
+        let repo = Repository::open(&repo_path).unwrap();
+        repo.config()
+            .unwrap()
+            .set_str("user.email", "email-only@example.com")
+            .unwrap();
+
+        fs::write(repo_path.join("context/test.csv"), "data").unwrap();
+        git_auto_commit(
+            repo_path.clone(),
+            vec!["context/test.csv".to_string()],
+            "Test commit".to_string(),
+            true,
+            false,
+        )
+        .unwrap();
+
+        let repo = Repository::open(&repo_path).unwrap();
+        let commit = repo.head().unwrap().peel_to_commit().unwrap();
+        assert_eq!(commit.author().name(), Some("unknown"));
+        assert_eq!(commit.author().email(), Some("email-only@example.com"));
+    }
+
+    #[test]
+    fn test_git_get_identity_reads_repo_local_config() {
+        let (_temp_dir, repo_path) = create_test_repo();
+        let repo = Repository::open(&repo_path).unwrap();
+        repo.config()
+            .unwrap()
+            .set_str("user.name", "Repo Author")
+            .unwrap();
+        repo.config()
+            .unwrap()
+            .set_str("user.email", "author@example.com")
+            .unwrap();
+
+        let identity = git_get_identity(repo_path, false).unwrap();
+        assert_eq!(identity.name, Some("Repo Author".to_string()));
+        assert_eq!(identity.email, Some("author@example.com".to_string()));
+    }
+
+    #[test]
+    fn test_git_set_identity_writes_repo_local_config() {
+        let (_temp_dir, repo_path) = create_test_repo();
+
+        git_set_identity(
+            repo_path.clone(),
+            false,
+            "Repo Author".to_string(),
+            "author@example.com".to_string(),
+        )
+        .unwrap();
+
+        let repo = Repository::open(&repo_path).unwrap();
+        let config = repo.config().unwrap();
+        assert_eq!(config.get_string("user.name").unwrap(), "Repo Author");
+        assert_eq!(
+            config.get_string("user.email").unwrap(),
+            "author@example.com"
+        );
+    }
+
+    #[test]
+    fn test_git_set_identity_rejects_invalid_email() {
+        let (_temp_dir, repo_path) = create_test_repo();
+
+        let result = git_set_identity(
+            repo_path,
+            false,
+            "Repo Author".to_string(),
+            "not-an-email".to_string(),
+        );
 
-        index.add_path(file_path).map_err(|e| {
-            log::error!("Failed to add {file_path:?} to index: {e}");
-            format!("Failed to add {file} to index: {e}")
-        })?;
+        assert!(result.is_err());
     }
 
-    // CRITICAL: Write index before creating tree (from practice-scout pitfall)
-    index.write().map_err(|e| {
-        log::error!("Failed to write index: {e}");
-        format!("Failed to write index: {e}")
-    })?;
+    #[test]
+    fn test_repo_statuses_cache_reused_within_ttl() {
+        let (_temp_dir, repo_path) = create_test_repo();
 
-    // Write tree from index
-    let tree_id = index.write_tree().map_err(|e| {
-        log::error!("Failed to write tree: {e}");
-        format!("Failed to write tree: {e}")
-    })?;
+        fs::write(repo_path.join("context/untracked.csv"), "a,b\n1,2").unwrap();
+        let first = repo_statuses(&repo_path).unwrap();
 
-    let tree = repo.find_tree(tree_id).map_err(|e| {
-        log::error!("Failed to find tree: {e}");
-        format!("Failed to find tree: {e}")
-    })?;
+        // A file created after the first call should not appear yet, since
+        // the cached snapshot is reused within the TTL window.
+        fs::write(repo_path.join("context/another.csv"), "c,d\n3,4").unwrap();
+        let second = repo_statuses(&repo_path).unwrap();
 
-    // Get signature (respects Git config, fallback to default)
-    let signature = repo
-        .signature()
-        .or_else(|_| {
-            log::debug!("Git user not configured, using default signature");
-            Signature::now("Unheard User", "user@unheard.local")
-        })
-        .map_err(|e| {
-            log::error!("Failed to create signature: {e}");
-            format!("Failed to create signature: {e}")
-        })?;
+        assert!(Arc::ptr_eq(&first, &second));
+    }
 
-    // Get parent commit (if exists)
-    let parent_commit = repo
-        .head()
-        .ok()
-        .and_then(|head| head.target().and_then(|oid| repo.find_commit(oid).ok()));
+    #[test]
+    fn test_git_auto_commit_unsigned_by_default() {
+        let (_temp_dir, repo_path) = create_test_repo();
 
-    // Create commit
-    let commit_id = if let Some(parent) = parent_commit {
-        // Subsequent commit with parent
-        repo.commit(
-            Some("HEAD"),
-            &signature,
-            &signature,
-            &message,
-            &tree,
-            &[&parent],
+        fs::write(repo_path.join("context/test.csv"), "data").unwrap();
+        let commit_hash = git_auto_commit(
+            repo_path.clone(),
+            vec!["context/test.csv".to_string()],
+            "Test commit".to_string(),
+            true,
+            false,
         )
-    } else {
-        // First commit (no parent)
-        repo.commit(Some("HEAD"), &signature, &signature, &message, &tree, &[])
-    }
-    .map_err(|e| {
-        log::error!("Failed to create commit: {e}");
-        format!("Failed to create commit: {e}")
-    })?;
+        .unwrap();
 
-    let commit_hash = commit_id.to_string();
-    log::info!("Created commit: {commit_hash}");
+        let status = git_verify_commit(repo_path, commit_hash).unwrap();
+        assert!(!status.signed);
+        assert!(!status.valid);
+        assert!(status.details.is_none());
+    }
 
-    Ok(commit_hash)
-}
+    #[test]
+    fn test_git_auto_commit_signing_enabled_without_key_fails() {
+        let (_temp_dir, repo_path) = create_test_repo();
+        let repo = Repository::open(&repo_path).unwrap();
+        repo.config().unwrap().set_bool("commit.gpgsign", true).unwrap();
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use std::fs;
-    use tempfile::TempDir;
+        fs::write(repo_path.join("context/test.csv"), "data").unwrap();
+        let result = git_auto_commit(
+            repo_path,
+            vec!["context/test.csv".to_string()],
+            "Test commit".to_string(),
+            true,
+            false,
+        );
 
-    /// Create a test repository with .gitattributes
-    fn create_test_repo() -> (TempDir, PathBuf) {
-        let temp_dir = TempDir::new().unwrap();
-        let repo_path = temp_dir.path().to_path_buf();
+        assert!(result.is_err());
+        assert!(result.unwrap_err().contains("user.signingkey"));
+    }
 
-        // Initialize repo
-        let repo = Repository::init(&repo_path).unwrap();
+    #[test]
+    fn test_git_verify_commit_unknown_id_errors() {
+        let (_temp_dir, repo_path) = create_test_repo();
+        let result = git_verify_commit(repo_path, "not-a-commit-id".to_string());
+        assert!(result.is_err());
+    }
 
-        // Create context directory
-        fs::create_dir_all(repo_path.join("context")).unwrap();
+    /// Generates a throwaway ed25519 keypair for `ssh-keygen -Y sign`/`-Y
+    /// check-novalidate`, skipping the test instead of failing it when
+    /// `ssh-keygen` isn't available in the sandbox running these tests.
+    fn generate_ssh_signing_key(dir: &Path) -> Option<PathBuf> {
+        let key_path = dir.join("id_ed25519");
+        let output = std::process::Command::new("ssh-keygen")
+            .args(["-t", "ed25519", "-N", "", "-f"])
+            .arg(&key_path)
+            .output()
+            .ok()?;
+        output.status.success().then_some(key_path)
+    }
 
-        // Create .gitattributes with LFS rules
-        let gitattributes_content = r#"# Git LFS tracking for large files
-context/**/*.pdf filter=lfs diff=lfs merge=lfs -text
-context/**/*.xlsx filter=lfs diff=lfs merge=lfs -text
-"#;
-        fs::write(repo_path.join(".gitattributes"), gitattributes_content).unwrap();
+    #[test]
+    #[cfg(unix)]
+    fn test_git_auto_commit_ssh_signing_round_trip() {
+        let (_temp_dir, repo_path) = create_test_repo();
+        let keys_dir = _temp_dir.path().join("keys");
+        fs::create_dir_all(&keys_dir).unwrap();
+        let Some(key_path) = generate_ssh_signing_key(&keys_dir) else {
+            return;
+        };
 
-        // Initial commit
-        let mut index = repo.index().unwrap();
-        index
-            .add_all(["."], git2::IndexAddOption::DEFAULT, None)
+        let repo = Repository::open(&repo_path).unwrap();
+        let mut config = repo.config().unwrap();
+        config.set_bool("commit.gpgsign", true).unwrap();
+        config.set_str("gpg.format", "ssh").unwrap();
+        config
+            .set_str("user.signingkey", key_path.to_str().unwrap())
             .unwrap();
-        index.write().unwrap();
-
-        let tree_id = index.write_tree().unwrap();
-        let tree = repo.find_tree(tree_id).unwrap();
-        let signature = Signature::now("Test User", "test@example.com").unwrap();
 
-        repo.commit(
-            Some("HEAD"),
-            &signature,
-            &signature,
-            "Initial commit",
-            &tree,
-            &[],
+        fs::write(repo_path.join("context/test.csv"), "data").unwrap();
+        let commit_hash = git_auto_commit(
+            repo_path.clone(),
+            vec!["context/test.csv".to_string()],
+            "Signed commit".to_string(),
+            true,
+            false,
         )
         .unwrap();
 
-        (temp_dir, repo_path)
+        let repo = Repository::open(&repo_path).unwrap();
+        let head_commit = repo.head().unwrap().peel_to_commit().unwrap();
+        assert_eq!(head_commit.id().to_string(), commit_hash);
+        assert_eq!(head_commit.message().unwrap(), "Signed commit");
+
+        let status = git_verify_commit(repo_path, commit_hash).unwrap();
+        assert!(status.signed);
+        assert!(status.valid);
+        assert!(status.signer.is_none());
     }
 
     #[test]
-    fn test_git_auto_commit_single_file() {
+    #[cfg(unix)]
+    fn test_git_verify_commit_ssh_signature_validates_against_allowed_signers_file() {
         let (_temp_dir, repo_path) = create_test_repo();
+        let keys_dir = _temp_dir.path().join("keys");
+        fs::create_dir_all(&keys_dir).unwrap();
+        let Some(key_path) = generate_ssh_signing_key(&keys_dir) else {
+            return;
+        };
+        let public_key = fs::read_to_string(format!("{}.pub", key_path.display())).unwrap();
 
-        // Create a CSV file
-        let csv_path = repo_path.join("context/test.csv");
-        fs::write(&csv_path, "name,age\nAlice,30\nBob,25").unwrap();
+        let author_email = "committer@example.com";
+        let allowed_signers_path = _temp_dir.path().join("allowed_signers");
+        fs::write(&allowed_signers_path, format!("{author_email} {}", public_key.trim())).unwrap();
 
-        // Commit the file
-        let result = git_auto_commit(
+        let repo = Repository::open(&repo_path).unwrap();
+        let mut config = repo.config().unwrap();
+        config.set_str("user.name", "Committer").unwrap();
+        config.set_str("user.email", author_email).unwrap();
+        config.set_bool("commit.gpgsign", true).unwrap();
+        config.set_str("gpg.format", "ssh").unwrap();
+        config
+            .set_str("user.signingkey", key_path.to_str().unwrap())
+            .unwrap();
+        config
+            .set_str("gpg.ssh.allowedSignersFile", allowed_signers_path.to_str().unwrap())
+            .unwrap();
+
+        fs::write(repo_path.join("context/test.csv"), "data").unwrap();
+        let commit_hash = git_auto_commit(
             repo_path.clone(),
             vec!["context/test.csv".to_string()],
-            "Add context: test.csv".to_string(),
-        );
-
-        assert!(result.is_ok());
-        let commit_hash = result.unwrap();
-        assert!(!commit_hash.is_empty());
-        assert_eq!(commit_hash.len(), 40); // Git SHA is 40 characters
+            "Signed commit".to_string(),
+            true,
+            false,
+        )
+        .unwrap();
 
-        // Verify commit was created
-        let repo = Repository::open(repo_path).unwrap();
-        let head = repo.head().unwrap();
-        let commit = head.peel_to_commit().unwrap();
-        assert_eq!(commit.message().unwrap(), "Add context: test.csv");
+        let status = git_verify_commit(repo_path, commit_hash).unwrap();
+        assert!(status.signed);
+        assert!(status.valid);
+        assert_eq!(status.signer.as_deref(), Some(author_email));
     }
 
     #[test]
-    fn test_git_auto_commit_multiple_files() {
+    fn test_git_auto_commit_amend_coalesces_same_file_resave() {
         let (_temp_dir, repo_path) = create_test_repo();
 
-        // Create multiple files
-        fs::write(repo_path.join("context/file1.csv"), "name,age\nAlice,30").unwrap();
-        fs::write(repo_path.join("context/file2.csv"), "name,score\nBob,95").unwrap();
+        fs::write(repo_path.join("context/test.csv"), "v1").unwrap();
+        let first_hash = git_auto_commit(
+            repo_path.clone(),
+            vec!["context/test.csv".to_string()],
+            "[unheard] Add context: test.csv".to_string(),
+            true,
+            true,
+        )
+        .unwrap();
 
-        // Commit multiple files
-        let result = git_auto_commit(
+        fs::write(repo_path.join("context/test.csv"), "v2").unwrap();
+        let second_hash = git_auto_commit(
             repo_path.clone(),
-            vec![
-                "context/file1.csv".to_string(),
-                "context/file2.csv".to_string(),
-            ],
-            "Add context files: file1.csv, file2.csv".to_string(),
-        );
+            vec!["context/test.csv".to_string()],
+            "[unheard] Add context: test.csv (edited)".to_string(),
+            true,
+            true,
+        )
+        .unwrap();
 
-        assert!(result.is_ok());
+        assert_ne!(first_hash, second_hash);
 
-        // Verify commit message
-        let repo = Repository::open(repo_path).unwrap();
+        let repo = Repository::open(&repo_path).unwrap();
         let head = repo.head().unwrap();
         let commit = head.peel_to_commit().unwrap();
+        assert_eq!(commit.id().to_string(), second_hash);
         assert_eq!(
             commit.message().unwrap(),
-            "Add context files: file1.csv, file2.csv"
+            "[unheard] Add context: test.csv (edited)"
         );
+        // The initial commit from `create_test_repo` is still HEAD's only parent;
+        // amending replaced the `[unheard]` commit in place rather than stacking
+        // a second one on top of it.
+        assert_eq!(commit.parent_count(), 1);
+        assert_eq!(commit.parent(0).unwrap().message().unwrap(), "Initial commit");
+
+        let content = fs::read_to_string(repo_path.join("context/test.csv")).unwrap();
+        assert_eq!(content, "v2");
     }
 
     #[test]
-    fn test_git_auto_commit_empty_files() {
+    fn test_git_auto_commit_amend_skipped_for_non_unheard_message() {
         let (_temp_dir, repo_path) = create_test_repo();
 
-        // Try to commit with empty file list
-        let result = git_auto_commit(repo_path, vec![], "Empty commit".to_string());
+        fs::write(repo_path.join("context/test.csv"), "v1").unwrap();
+        git_auto_commit(
+            repo_path.clone(),
+            vec!["context/test.csv".to_string()],
+            "Manual commit message".to_string(),
+            true,
+            true,
+        )
+        .unwrap();
 
-        assert!(result.is_err());
-        assert!(result.unwrap_err().contains("No files provided"));
+        fs::write(repo_path.join("context/test.csv"), "v2").unwrap();
+        git_auto_commit(
+            repo_path.clone(),
+            vec!["context/test.csv".to_string()],
+            "[unheard] Add context: test.csv".to_string(),
+            true,
+            true,
+        )
+        .unwrap();
+
+        let repo = Repository::open(&repo_path).unwrap();
+        let commit = repo.head().unwrap().peel_to_commit().unwrap();
+        assert_eq!(commit.parent_count(), 1);
+        assert_eq!(
+            commit.parent(0).unwrap().message().unwrap(),
+            "Manual commit message"
+        );
     }
 
     #[test]
-    fn test_git_auto_commit_empty_message() {
+    fn test_git_auto_commit_amend_skipped_for_different_file() {
         let (_temp_dir, repo_path) = create_test_repo();
 
-        // Create a file
-        fs::write(repo_path.join("context/test.csv"), "data").unwrap();
+        fs::write(repo_path.join("context/a.csv"), "a").unwrap();
+        git_auto_commit(
+            repo_path.clone(),
+            vec!["context/a.csv".to_string()],
+            "[unheard] Add context: a.csv".to_string(),
+            true,
+            true,
+        )
+        .unwrap();
 
-        // Try to commit with empty message
-        let result = git_auto_commit(
-            repo_path,
-            vec!["context/test.csv".to_string()],
-            "   ".to_string(),
-        );
+        fs::write(repo_path.join("context/b.csv"), "b").unwrap();
+        git_auto_commit(
+            repo_path.clone(),
+            vec!["context/b.csv".to_string()],
+            "[unheard] Add context: b.csv".to_string(),
+            true,
+            true,
+        )
+        .unwrap();
 
-        assert!(result.is_err());
-        assert!(result
-            .unwrap_err()
-            .contains("Commit message cannot be empty"));
+        let repo = Repository::open(&repo_path).unwrap();
+        let commit = repo.head().unwrap().peel_to_commit().unwrap();
+        assert_eq!(commit.parent_count(), 1);
+        assert_eq!(
+            commit.parent(0).unwrap().message().unwrap(),
+            "[unheard] Add context: a.csv"
+        );
     }
 
     #[test]
-    fn test_git_auto_commit_missing_repo() {
-        let temp_dir = TempDir::new().unwrap();
-        let non_repo_path = temp_dir.path().to_path_buf();
+    fn test_git_auto_commit_amend_false_never_coalesces() {
+        let (_temp_dir, repo_path) = create_test_repo();
 
-        let result = git_auto_commit(
-            non_repo_path,
-            vec!["test.csv".to_string()],
-            "Test commit".to_string(),
-        );
+        fs::write(repo_path.join("context/test.csv"), "v1").unwrap();
+        git_auto_commit(
+            repo_path.clone(),
+            vec!["context/test.csv".to_string()],
+            "[unheard] Add context: test.csv".to_string(),
+            true,
+            true,
+        )
+        .unwrap();
 
-        assert!(result.is_err());
-        assert!(result
-            .unwrap_err()
-            .contains("Failed to open Git repository"));
+        fs::write(repo_path.join("context/test.csv"), "v2").unwrap();
+        git_auto_commit(
+            repo_path.clone(),
+            vec!["context/test.csv".to_string()],
+            "[unheard] Add context: test.csv (edited)".to_string(),
+            true,
+            false,
+        )
+        .unwrap();
+
+        let repo = Repository::open(&repo_path).unwrap();
+        let commit = repo.head().unwrap().peel_to_commit().unwrap();
+        assert_eq!(commit.parent_count(), 1);
+        assert_eq!(
+            commit.parent(0).unwrap().message().unwrap(),
+            "[unheard] Add context: test.csv"
+        );
     }
 
     #[test]
-    fn test_git_auto_commit_index_write_called() {
+    fn test_git_auto_commit_amend_skipped_when_head_already_pushed() {
         let (_temp_dir, repo_path) = create_test_repo();
+        let (_upstream_dir, upstream_path) = create_test_repo();
 
-        // Create a file
-        fs::write(repo_path.join("context/test.csv"), "data").unwrap();
+        fs::write(repo_path.join("context/test.csv"), "v1").unwrap();
+        git_auto_commit(
+            repo_path.clone(),
+            vec!["context/test.csv".to_string()],
+            "[unheard] Add context: test.csv".to_string(),
+            true,
+            true,
+        )
+        .unwrap();
 
-        // Commit should succeed (index.write() is called before write_tree())
-        let result = git_auto_commit(
-            repo_path,
+        let repo = Repository::open(&repo_path).unwrap();
+        let head_oid = repo.head().unwrap().target().unwrap();
+        repo.reference(
+            "refs/remotes/origin/master",
+            head_oid,
+            true,
+            "simulate a push",
+        )
+        .unwrap();
+        repo.config()
+            .unwrap()
+            .set_str("branch.master.remote", "origin")
+            .unwrap();
+        repo.config()
+            .unwrap()
+            .set_str("branch.master.merge", "refs/heads/master")
+            .unwrap();
+        let _ = &upstream_path; // only needed to construct a throwaway TempDir above
+
+        fs::write(repo_path.join("context/test.csv"), "v2").unwrap();
+        git_auto_commit(
+            repo_path.clone(),
             vec!["context/test.csv".to_string()],
-            "Test commit".to_string(),
-        );
+            "[unheard] Add context: test.csv (edited)".to_string(),
+            true,
+            true,
+        )
+        .unwrap();
 
-        assert!(result.is_ok());
+        let repo = Repository::open(&repo_path).unwrap();
+        let commit = repo.head().unwrap().peel_to_commit().unwrap();
+        // Since HEAD was already "pushed" (the upstream ref pointed at it),
+        // amending was skipped in favor of a new commit on top.
+        assert_eq!(commit.parent_count(), 1);
+        assert_eq!(
+            commit.parent(0).unwrap().message().unwrap(),
+            "[unheard] Add context: test.csv"
+        );
     }
 
     #[test]
-    fn test_git_auto_commit_lfs_file() {
+    #[cfg(unix)]
+    fn test_git_auto_commit_amend_is_signed_when_signing_enabled() {
         let (_temp_dir, repo_path) = create_test_repo();
+        let keys_dir = _temp_dir.path().join("keys");
+        fs::create_dir_all(&keys_dir).unwrap();
+        let Some(key_path) = generate_ssh_signing_key(&keys_dir) else {
+            return;
+        };
 
-        // Create a PDF file (LFS-tracked via .gitattributes)
-        // Note: Actual LFS tracking requires git-lfs to be installed
-        // This test verifies the commit succeeds regardless of LFS availability
-        let pdf_path = repo_path.join("context/test.pdf");
-        fs::write(&pdf_path, b"%PDF-1.4\nMock PDF content").unwrap();
+        let repo = Repository::open(&repo_path).unwrap();
+        let mut config = repo.config().unwrap();
+        config.set_bool("commit.gpgsign", true).unwrap();
+        config.set_str("gpg.format", "ssh").unwrap();
+        config
+            .set_str("user.signingkey", key_path.to_str().unwrap())
+            .unwrap();
 
-        let result = git_auto_commit(
-            repo_path,
-            vec!["context/test.pdf".to_string()],
-            "Add context: test.pdf".to_string(),
-        );
+        fs::write(repo_path.join("context/test.csv"), "v1").unwrap();
+        git_auto_commit(
+            repo_path.clone(),
+            vec!["context/test.csv".to_string()],
+            "[unheard] Add context: test.csv".to_string(),
+            true,
+            true,
+        )
+        .unwrap();
 
-        // Should succeed even if LFS is not installed (Git falls back to normal storage)
-        assert!(result.is_ok());
+        fs::write(repo_path.join("context/test.csv"), "v2").unwrap();
+        let amended_hash = git_auto_commit(
+            repo_path.clone(),
+            vec!["context/test.csv".to_string()],
+            "[unheard] Add context: test.csv (edited)".to_string(),
+            true,
+            true,
+        )
+        .unwrap();
+
+        let repo = Repository::open(&repo_path).unwrap();
+        let commit = repo.head().unwrap().peel_to_commit().unwrap();
+        assert_eq!(commit.id().to_string(), amended_hash);
+        // Still amended in place (one commit on top of the initial commit),
+        // but the amended commit is signed rather than written unsigned.
+        assert_eq!(commit.parent_count(), 1);
+        assert_eq!(commit.parent(0).unwrap().message().unwrap(), "Initial commit");
+
+        let status = git_verify_commit(repo_path, amended_hash).unwrap();
+        assert!(status.signed);
+        assert!(status.valid);
     }
 }
@@ -0,0 +1,720 @@
+//! SQLite index of decision logs and Attio imports.
+//!
+//! Decision logs live as markdown-with-frontmatter under `decisions/` and
+//! Attio records as JSON under `attio/<type>/`, so there's no way to query
+//! either without scanning the filesystem. This module keeps a small SQLite
+//! database (`.unheard/index.sqlite3`, inside the project directory) in sync
+//! with them: [`index_decision`] and [`index_attio_record`] are called
+//! best-effort from `create_decision_log`, `save_attio_import`, and
+//! `batch_save_attio_imports` after each Git commit attempt, and
+//! [`reindex`] rebuilds the database from scratch by walking the committed
+//! tree, for when the project was edited by hand outside the app.
+
+use crate::commands::git::get_file_history;
+use git2::{Repository, Tree};
+use rusqlite::{named_params, params, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::path::{Path, PathBuf};
+
+/// Path to the index database, relative to the project root.
+const INDEX_DB_PATH: &str = ".unheard/index.sqlite3";
+
+/// Open the project's index database, creating its schema on first use.
+fn open_index(project_path: &Path) -> Result<Connection, String> {
+    let db_path = project_path.join(INDEX_DB_PATH);
+    if let Some(dir) = db_path.parent() {
+        std::fs::create_dir_all(dir)
+            .map_err(|e| format!("Failed to create index directory: {e}"))?;
+    }
+
+    let conn = Connection::open(&db_path)
+        .map_err(|e| format!("Failed to open index database: {e}"))?;
+    create_schema(&conn)?;
+    Ok(conn)
+}
+
+fn create_schema(conn: &Connection) -> Result<(), String> {
+    conn.execute_batch(
+        "CREATE TABLE IF NOT EXISTS decisions (
+            relative_path TEXT PRIMARY KEY,
+            id TEXT,
+            title TEXT,
+            status TEXT,
+            date TEXT,
+            commit_hash TEXT
+        );
+        CREATE TABLE IF NOT EXISTS attio_records (
+            relative_path TEXT PRIMARY KEY,
+            object_type TEXT NOT NULL,
+            record_id TEXT NOT NULL,
+            name TEXT,
+            commit_hash TEXT
+        );
+        CREATE INDEX IF NOT EXISTS idx_attio_records_lookup
+            ON attio_records (object_type, record_id);",
+    )
+    .map_err(|e| format!("Failed to create index schema: {e}"))
+}
+
+/// Run `body` inside a SQLite transaction, committing on success. `body`
+/// returning `Err` leaves the transaction to roll back when it's dropped.
+fn transaction<T>(
+    conn: &mut Connection,
+    body: impl FnOnce(&rusqlite::Transaction) -> Result<T, String>,
+) -> Result<T, String> {
+    let tx = conn
+        .transaction()
+        .map_err(|e| format!("Failed to start index transaction: {e}"))?;
+    let result = body(&tx)?;
+    tx.commit()
+        .map_err(|e| format!("Failed to commit index transaction: {e}"))?;
+    Ok(result)
+}
+
+// ============================================================================
+// Frontmatter / JSON field extraction
+// ============================================================================
+
+/// The handful of decision-frontmatter fields the index cares about.
+#[derive(Debug, Default)]
+struct DecisionFrontmatter {
+    id: Option<String>,
+    title: Option<String>,
+    status: Option<String>,
+    date: Option<String>,
+}
+
+/// Parse a decision log's leading `---`-delimited YAML frontmatter. Decision
+/// frontmatter is always a flat `key: value` block (see `decisions.rs`), so
+/// this reads lines rather than pulling in a full YAML parser.
+fn parse_decision_frontmatter(content: &str) -> DecisionFrontmatter {
+    let mut frontmatter = DecisionFrontmatter::default();
+
+    let Some(rest) = content.strip_prefix("---\n") else {
+        return frontmatter;
+    };
+    let Some(end) = rest.find("\n---") else {
+        return frontmatter;
+    };
+
+    for line in rest[..end].lines() {
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        let value = value.trim().trim_matches('"').to_string();
+        match key.trim() {
+            "id" => frontmatter.id = Some(value),
+            "title" => frontmatter.title = Some(value),
+            "status" => frontmatter.status = Some(value),
+            "date" => frontmatter.date = Some(value),
+            _ => {}
+        }
+    }
+
+    frontmatter
+}
+
+/// Fields read out of an Attio record's JSON body, as opposed to the
+/// `object_type`/`record_id` a live `save_attio_import` call already has as
+/// arguments. `name` is read on every index; `record_id` is only used
+/// during [`reindex`], which has nothing but the file content to go on.
+#[derive(Debug, Default)]
+struct AttioFields {
+    record_id: Option<String>,
+    name: Option<String>,
+}
+
+/// Attio payloads observed in this codebase use camelCase (`recordId`), but
+/// read the snake_case spelling too in case of a hand-edited file.
+fn parse_attio_fields(json_content: &str) -> AttioFields {
+    let Ok(value) = serde_json::from_str::<serde_json::Value>(json_content) else {
+        return AttioFields::default();
+    };
+
+    let record_id = value
+        .get("recordId")
+        .or_else(|| value.get("record_id"))
+        .and_then(|v| v.as_str())
+        .map(str::to_string);
+    let name = value.get("name").and_then(|v| v.as_str()).map(str::to_string);
+
+    AttioFields { record_id, name }
+}
+
+// ============================================================================
+// Upserts
+// ============================================================================
+
+fn upsert_decision(
+    tx: &rusqlite::Transaction,
+    relative_path: &str,
+    frontmatter: &DecisionFrontmatter,
+    commit_hash: Option<&str>,
+) -> Result<(), String> {
+    tx.execute(
+        "INSERT INTO decisions (relative_path, id, title, status, date, commit_hash)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+         ON CONFLICT(relative_path) DO UPDATE SET
+             id = excluded.id, title = excluded.title, status = excluded.status,
+             date = excluded.date, commit_hash = excluded.commit_hash",
+        params![
+            relative_path,
+            frontmatter.id,
+            frontmatter.title,
+            frontmatter.status,
+            frontmatter.date,
+            commit_hash
+        ],
+    )
+    .map_err(|e| format!("Failed to index decision {relative_path}: {e}"))?;
+    Ok(())
+}
+
+fn upsert_attio_record(
+    tx: &rusqlite::Transaction,
+    relative_path: &str,
+    object_type: &str,
+    record_id: &str,
+    name: Option<&str>,
+    commit_hash: Option<&str>,
+) -> Result<(), String> {
+    tx.execute(
+        "INSERT INTO attio_records (relative_path, object_type, record_id, name, commit_hash)
+         VALUES (?1, ?2, ?3, ?4, ?5)
+         ON CONFLICT(relative_path) DO UPDATE SET
+             object_type = excluded.object_type, record_id = excluded.record_id,
+             name = excluded.name, commit_hash = excluded.commit_hash",
+        params![relative_path, object_type, record_id, name, commit_hash],
+    )
+    .map_err(|e| format!("Failed to index Attio record {relative_path}: {e}"))?;
+    Ok(())
+}
+
+/// Parse `content`'s frontmatter and upsert it into the index, keyed by
+/// `relative_path`. Called from `create_decision_log` after its Git commit
+/// attempt; `commit_hash` is `None` when that commit failed, matching the
+/// "saved but not committed" guarantee -- indexing failures are logged but
+/// never fail the caller's save.
+pub fn index_decision(
+    project_path: &Path,
+    relative_path: &str,
+    content: &str,
+    commit_hash: Option<&str>,
+) -> Result<(), String> {
+    let frontmatter = parse_decision_frontmatter(content);
+    let mut conn = open_index(project_path)?;
+    transaction(&mut conn, |tx| {
+        upsert_decision(tx, relative_path, &frontmatter, commit_hash)
+    })
+}
+
+/// Parse `json_content`'s `name` field and upsert it into the index, keyed
+/// by `relative_path`. `object_type`/`record_id` are taken from the caller's
+/// own arguments rather than re-parsed from JSON, since `save_attio_import`
+/// and `batch_save_attio_imports` already have them authoritatively.
+pub fn index_attio_record(
+    project_path: &Path,
+    relative_path: &str,
+    object_type: &str,
+    record_id: &str,
+    json_content: &str,
+    commit_hash: Option<&str>,
+) -> Result<(), String> {
+    let fields = parse_attio_fields(json_content);
+    let mut conn = open_index(project_path)?;
+    transaction(&mut conn, |tx| {
+        upsert_attio_record(
+            tx,
+            relative_path,
+            object_type,
+            record_id,
+            fields.name.as_deref(),
+            commit_hash,
+        )
+    })
+}
+
+// ============================================================================
+// Query commands
+// ============================================================================
+
+/// An indexed decision log, as stored by [`index_decision`].
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct DecisionIndexEntry {
+    pub relative_path: String,
+    pub id: Option<String>,
+    pub title: Option<String>,
+    pub status: Option<String>,
+    pub date: Option<String>,
+    pub commit_hash: Option<String>,
+}
+
+fn decision_entry_from_row(row: &rusqlite::Row) -> rusqlite::Result<DecisionIndexEntry> {
+    Ok(DecisionIndexEntry {
+        relative_path: row.get(0)?,
+        id: row.get(1)?,
+        title: row.get(2)?,
+        status: row.get(3)?,
+        date: row.get(4)?,
+        commit_hash: row.get(5)?,
+    })
+}
+
+/// An indexed Attio record, as stored by [`index_attio_record`].
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct AttioIndexEntry {
+    pub relative_path: String,
+    pub object_type: String,
+    pub record_id: String,
+    pub name: Option<String>,
+    pub commit_hash: Option<String>,
+}
+
+fn attio_entry_from_row(row: &rusqlite::Row) -> rusqlite::Result<AttioIndexEntry> {
+    Ok(AttioIndexEntry {
+        relative_path: row.get(0)?,
+        object_type: row.get(1)?,
+        record_id: row.get(2)?,
+        name: row.get(3)?,
+        commit_hash: row.get(4)?,
+    })
+}
+
+/// List indexed decision logs, most recently dated first, optionally
+/// restricted to a single `status` (e.g. "ready", "draft").
+#[tauri::command]
+#[specta::specta]
+pub fn list_decisions(
+    project_path: PathBuf,
+    status_filter: Option<String>,
+) -> Result<Vec<DecisionIndexEntry>, String> {
+    log::info!("Listing decisions index for {project_path:?} (status_filter={status_filter:?})");
+
+    let conn = open_index(&project_path)?;
+    let mut stmt = conn
+        .prepare(
+            "SELECT relative_path, id, title, status, date, commit_hash FROM decisions
+             WHERE :status_filter IS NULL OR status = :status_filter
+             ORDER BY date DESC",
+        )
+        .map_err(|e| format!("Failed to query decisions index: {e}"))?;
+
+    let rows = stmt
+        .query_map(named_params! { ":status_filter": status_filter }, decision_entry_from_row)
+        .map_err(|e| format!("Failed to query decisions index: {e}"))?;
+
+    rows.collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Failed to read decisions index: {e}"))
+}
+
+/// Look up the indexed Attio record for `object_type`/`record_id`, or
+/// `None` if it hasn't been imported (or indexed) yet.
+#[tauri::command]
+#[specta::specta]
+pub fn find_attio_record(
+    project_path: PathBuf,
+    object_type: String,
+    record_id: String,
+) -> Result<Option<AttioIndexEntry>, String> {
+    log::info!("Looking up Attio record {object_type}/{record_id} in {project_path:?}");
+
+    let conn = open_index(&project_path)?;
+    conn.query_row(
+        "SELECT relative_path, object_type, record_id, name, commit_hash FROM attio_records
+         WHERE object_type = ?1 AND record_id = ?2",
+        params![object_type, record_id],
+        attio_entry_from_row,
+    )
+    .optional()
+    .map_err(|e| format!("Failed to query Attio index: {e}"))
+}
+
+/// Combined search results across both indexed tables.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct IndexSearchResults {
+    pub decisions: Vec<DecisionIndexEntry>,
+    pub attio_records: Vec<AttioIndexEntry>,
+}
+
+/// Case-insensitive substring search over decision titles and Attio record
+/// names, so the UI can offer one search box across both without the caller
+/// needing to know which kind of record it's looking for.
+#[tauri::command]
+#[specta::specta]
+pub fn search_index(project_path: PathBuf, query: String) -> Result<IndexSearchResults, String> {
+    log::info!("Searching decision/Attio index in {project_path:?} for {query:?}");
+
+    let escaped = query.replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_");
+    let pattern = format!("%{escaped}%");
+    let conn = open_index(&project_path)?;
+
+    let mut decision_stmt = conn
+        .prepare(
+            "SELECT relative_path, id, title, status, date, commit_hash FROM decisions
+             WHERE title LIKE ?1 ESCAPE '\\' COLLATE NOCASE
+             ORDER BY date DESC",
+        )
+        .map_err(|e| format!("Failed to search decisions index: {e}"))?;
+    let decisions = decision_stmt
+        .query_map(params![pattern], decision_entry_from_row)
+        .map_err(|e| format!("Failed to search decisions index: {e}"))?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Failed to read decisions index: {e}"))?;
+
+    let mut attio_stmt = conn
+        .prepare(
+            "SELECT relative_path, object_type, record_id, name, commit_hash FROM attio_records
+             WHERE name LIKE ?1 ESCAPE '\\' COLLATE NOCASE",
+        )
+        .map_err(|e| format!("Failed to search Attio index: {e}"))?;
+    let attio_records = attio_stmt
+        .query_map(params![pattern], attio_entry_from_row)
+        .map_err(|e| format!("Failed to search Attio index: {e}"))?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Failed to read Attio index: {e}"))?;
+
+    Ok(IndexSearchResults { decisions, attio_records })
+}
+
+// ============================================================================
+// Reindex
+// ============================================================================
+
+/// How many rows a full [`reindex`] rebuilt.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ReindexSummary {
+    pub decisions_indexed: u32,
+    pub attio_records_indexed: u32,
+}
+
+/// Read a blob's bytes as UTF-8 text at `relative_path` in `tree`.
+fn read_blob_at_path(
+    repo: &Repository,
+    tree: &Tree,
+    relative_path: &str,
+) -> Result<String, String> {
+    let entry = tree
+        .get_path(Path::new(relative_path))
+        .map_err(|e| format!("Failed to find {relative_path} in HEAD tree: {e}"))?;
+    let blob = entry
+        .to_object(repo)
+        .map_err(|e| format!("Failed to read {relative_path}: {e}"))?
+        .into_blob()
+        .map_err(|_| format!("{relative_path} is not a blob"))?;
+
+    String::from_utf8(blob.content().to_vec())
+        .map_err(|e| format!("{relative_path} is not valid UTF-8: {e}"))
+}
+
+/// Rebuild the index from scratch by walking `HEAD`'s tree, discarding
+/// whatever rows were there before. Use this to bring the index back in
+/// sync after decision logs or Attio records were added, edited, or removed
+/// outside the app (e.g. a manual Git commit, or a fresh checkout).
+#[tauri::command]
+#[specta::specta]
+pub fn reindex(project_path: PathBuf) -> Result<ReindexSummary, String> {
+    log::info!("Reindexing {project_path:?}");
+
+    let repo = Repository::open(&project_path)
+        .map_err(|e| format!("Failed to open Git repository: {e}"))?;
+    let head_tree = repo
+        .head()
+        .and_then(|head| head.peel_to_tree())
+        .map_err(|e| format!("Failed to read HEAD tree: {e}"))?;
+
+    let mut decision_paths = Vec::new();
+    let mut attio_paths = Vec::new();
+    head_tree
+        .walk(git2::TreeWalkMode::PreOrder, |dir, entry| {
+            if entry.kind() != Some(git2::ObjectType::Blob) {
+                return 0;
+            }
+            let Some(name) = entry.name() else {
+                return 0;
+            };
+            let full_path = format!("{dir}{name}");
+            if full_path.starts_with("decisions/") && full_path.ends_with(".md") {
+                decision_paths.push(full_path);
+            } else if full_path.starts_with("attio/") && full_path.ends_with(".json") {
+                attio_paths.push(full_path);
+            }
+            0
+        })
+        .map_err(|e| format!("Failed to walk HEAD tree: {e}"))?;
+
+    let mut conn = open_index(&project_path)?;
+    let mut decisions_indexed = 0u32;
+    let mut attio_records_indexed = 0u32;
+
+    transaction(&mut conn, |tx| {
+        tx.execute("DELETE FROM decisions", [])
+            .map_err(|e| format!("Failed to clear decisions index: {e}"))?;
+        tx.execute("DELETE FROM attio_records", [])
+            .map_err(|e| format!("Failed to clear Attio index: {e}"))?;
+
+        for relative_path in &decision_paths {
+            let Ok(content) = read_blob_at_path(&repo, &head_tree, relative_path) else {
+                continue;
+            };
+            let frontmatter = parse_decision_frontmatter(&content);
+            let commit_hash = latest_commit_touching(&project_path, relative_path);
+            upsert_decision(tx, relative_path, &frontmatter, commit_hash.as_deref())?;
+            decisions_indexed += 1;
+        }
+
+        for relative_path in &attio_paths {
+            let Ok(content) = read_blob_at_path(&repo, &head_tree, relative_path) else {
+                continue;
+            };
+            let Some(object_type) = relative_path
+                .strip_prefix("attio/")
+                .and_then(|rest| rest.split_once('/'))
+                .map(|(object_type, _)| object_type)
+            else {
+                continue;
+            };
+            let fields = parse_attio_fields(&content);
+            let Some(record_id) = fields.record_id else {
+                log::warn!("Skipping {relative_path} during reindex: no record id in its JSON");
+                continue;
+            };
+            let commit_hash = latest_commit_touching(&project_path, relative_path);
+            upsert_attio_record(
+                tx,
+                relative_path,
+                object_type,
+                &record_id,
+                fields.name.as_deref(),
+                commit_hash.as_deref(),
+            )?;
+            attio_records_indexed += 1;
+        }
+
+        Ok(())
+    })?;
+
+    log::info!(
+        "Reindexed {decisions_indexed} decision(s) and {attio_records_indexed} Attio record(s)"
+    );
+
+    Ok(ReindexSummary { decisions_indexed, attio_records_indexed })
+}
+
+/// Most recent commit (from `HEAD`) that touched `relative_path`, used to
+/// populate each row's `commit_hash` during a full [`reindex`].
+fn latest_commit_touching(project_path: &Path, relative_path: &str) -> Option<String> {
+    get_file_history(project_path.to_path_buf(), relative_path.to_string())
+        .ok()?
+        .into_iter()
+        .next()
+        .map(|entry| entry.commit_hash)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use git2::Signature;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn create_test_repo() -> (TempDir, PathBuf) {
+        let temp_dir = TempDir::new().unwrap();
+        let repo_path = temp_dir.path().to_path_buf();
+
+        let repo = Repository::init(&repo_path).unwrap();
+        fs::write(repo_path.join(".gitkeep"), "").unwrap();
+
+        let mut index = repo.index().unwrap();
+        index.add_all(["."], git2::IndexAddOption::DEFAULT, None).unwrap();
+        index.write().unwrap();
+
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let signature = Signature::now("Test User", "test@example.com").unwrap();
+        repo.commit(Some("HEAD"), &signature, &signature, "Initial commit", &tree, &[]).unwrap();
+
+        (temp_dir, repo_path)
+    }
+
+    fn commit_file(repo_path: &Path, relative_path: &str, content: &str, message: &str) -> String {
+        let repo = Repository::open(repo_path).unwrap();
+        let file_path = repo_path.join(relative_path);
+        fs::create_dir_all(file_path.parent().unwrap()).unwrap();
+        fs::write(&file_path, content).unwrap();
+
+        let mut index = repo.index().unwrap();
+        index.add_path(Path::new(relative_path)).unwrap();
+        index.write().unwrap();
+
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let signature = Signature::now("Test User", "test@example.com").unwrap();
+        let parent = repo.head().unwrap().peel_to_commit().unwrap();
+        repo.commit(Some("HEAD"), &signature, &signature, message, &tree, &[&parent])
+            .unwrap()
+            .to_string()
+    }
+
+    #[test]
+    fn test_parse_decision_frontmatter_reads_flat_fields() {
+        let content =
+            "---\nid: dec-1\ntitle: Test Decision\nstatus: ready\ndate: 2026-02-04\n---\n\nBody.";
+        let frontmatter = parse_decision_frontmatter(content);
+
+        assert_eq!(frontmatter.id, Some("dec-1".to_string()));
+        assert_eq!(frontmatter.title, Some("Test Decision".to_string()));
+        assert_eq!(frontmatter.status, Some("ready".to_string()));
+        assert_eq!(frontmatter.date, Some("2026-02-04".to_string()));
+    }
+
+    #[test]
+    fn test_parse_decision_frontmatter_missing_delimiters() {
+        let frontmatter = parse_decision_frontmatter("# Just a heading\n\nNo frontmatter here.");
+        assert!(frontmatter.title.is_none());
+    }
+
+    #[test]
+    fn test_parse_attio_fields_reads_camel_case() {
+        let json = r#"{"objectType":"company","recordId":"uuid-1","name":"Acme Corp"}"#;
+        let fields = parse_attio_fields(json);
+        assert_eq!(fields.record_id, Some("uuid-1".to_string()));
+        assert_eq!(fields.name, Some("Acme Corp".to_string()));
+    }
+
+    #[test]
+    fn test_index_decision_then_list_decisions() {
+        let (_temp_dir, repo_path) = create_test_repo();
+        let content =
+            "---\nid: dec-1\ntitle: Test Decision\nstatus: ready\ndate: 2026-02-04\n---\n\nBody.";
+
+        index_decision(&repo_path, "decisions/dec-1.md", content, Some("abc123")).unwrap();
+
+        let decisions = list_decisions(repo_path, None).unwrap();
+        assert_eq!(decisions.len(), 1);
+        assert_eq!(decisions[0].title, Some("Test Decision".to_string()));
+        assert_eq!(decisions[0].commit_hash, Some("abc123".to_string()));
+    }
+
+    #[test]
+    fn test_list_decisions_filters_by_status() {
+        let (_temp_dir, repo_path) = create_test_repo();
+        index_decision(&repo_path, "decisions/a.md", "---\nstatus: draft\n---\n", None).unwrap();
+        index_decision(&repo_path, "decisions/b.md", "---\nstatus: ready\n---\n", None).unwrap();
+
+        let ready = list_decisions(repo_path, Some("ready".to_string())).unwrap();
+        assert_eq!(ready.len(), 1);
+        assert_eq!(ready[0].relative_path, "decisions/b.md");
+    }
+
+    #[test]
+    fn test_index_attio_record_then_find_it() {
+        let (_temp_dir, repo_path) = create_test_repo();
+        let json_content = r#"{"objectType":"company","recordId":"uuid-1","name":"Acme Corp"}"#;
+
+        index_attio_record(
+            &repo_path,
+            "attio/company/acme.json",
+            "company",
+            "uuid-1",
+            json_content,
+            None,
+        )
+        .unwrap();
+
+        let found = find_attio_record(repo_path, "company".to_string(), "uuid-1".to_string())
+            .unwrap()
+            .expect("record should be indexed");
+        assert_eq!(found.name, Some("Acme Corp".to_string()));
+    }
+
+    #[test]
+    fn test_find_attio_record_not_indexed_returns_none() {
+        let (_temp_dir, repo_path) = create_test_repo();
+        let found =
+            find_attio_record(repo_path, "company".to_string(), "missing".to_string()).unwrap();
+        assert!(found.is_none());
+    }
+
+    #[test]
+    fn test_index_decision_upserts_on_repeated_save() {
+        let (_temp_dir, repo_path) = create_test_repo();
+        index_decision(&repo_path, "decisions/dec-1.md", "---\nstatus: draft\n---\n", None)
+            .unwrap();
+        index_decision(&repo_path, "decisions/dec-1.md", "---\nstatus: ready\n---\n", Some("abc"))
+            .unwrap();
+
+        let decisions = list_decisions(repo_path, None).unwrap();
+        assert_eq!(decisions.len(), 1);
+        assert_eq!(decisions[0].status, Some("ready".to_string()));
+    }
+
+    #[test]
+    fn test_search_index_matches_decisions_and_attio_records() {
+        let (_temp_dir, repo_path) = create_test_repo();
+        index_decision(
+            &repo_path,
+            "decisions/dec-1.md",
+            "---\ntitle: Acme Seed Round\nstatus: ready\n---\n",
+            None,
+        )
+        .unwrap();
+        index_attio_record(
+            &repo_path,
+            "attio/company/acme.json",
+            "company",
+            "uuid-1",
+            r#"{"name":"Acme Corp"}"#,
+            None,
+        )
+        .unwrap();
+
+        let results = search_index(repo_path, "acme".to_string()).unwrap();
+        assert_eq!(results.decisions.len(), 1);
+        assert_eq!(results.attio_records.len(), 1);
+    }
+
+    #[test]
+    fn test_reindex_rebuilds_from_committed_tree() {
+        let (_temp_dir, repo_path) = create_test_repo();
+        commit_file(
+            &repo_path,
+            "decisions/dec-1.md",
+            "---\nid: dec-1\ntitle: Test Decision\nstatus: ready\ndate: 2026-02-04\n---\n",
+            "Create decision: dec-1.md",
+        );
+        let commit_hash = commit_file(
+            &repo_path,
+            "attio/company/acme.json",
+            r#"{"objectType":"company","recordId":"uuid-1","name":"Acme Corp"}"#,
+            "Import Attio company: acme",
+        );
+
+        let summary = reindex(repo_path.clone()).unwrap();
+        assert_eq!(summary.decisions_indexed, 1);
+        assert_eq!(summary.attio_records_indexed, 1);
+
+        let decisions = list_decisions(repo_path.clone(), None).unwrap();
+        assert_eq!(decisions[0].title, Some("Test Decision".to_string()));
+
+        let record = find_attio_record(repo_path, "company".to_string(), "uuid-1".to_string())
+            .unwrap()
+            .expect("record should be reindexed");
+        assert_eq!(record.commit_hash, Some(commit_hash));
+    }
+
+    #[test]
+    fn test_reindex_drops_stale_rows_no_longer_in_head() {
+        let (_temp_dir, repo_path) = create_test_repo();
+        index_decision(&repo_path, "decisions/stale.md", "---\nstatus: draft\n---\n", None)
+            .unwrap();
+
+        let summary = reindex(repo_path.clone()).unwrap();
+        assert_eq!(summary.decisions_indexed, 0);
+
+        let decisions = list_decisions(repo_path, None).unwrap();
+        assert!(decisions.is_empty());
+    }
+}
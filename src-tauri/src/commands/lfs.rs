@@ -0,0 +1,366 @@
+//! Git LFS tracking and pointer detection commands.
+//!
+//! Goes beyond the static `.gitattributes` rules written by `initialize_git`:
+//! `track_lfs_pattern` records new patterns and runs `git lfs track`,
+//! `migrate_to_lfs` rewrites already-committed large files into LFS
+//! pointers, [`store_lfs_object`] is what `upload_context_file` uses to
+//! write oversized uploads into a local LFS object store and get back the
+//! pointer to commit instead, and [`is_lfs_pointer`] lets the file listing
+//! tell a real blob from an unresolved pointer.
+
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Header that identifies a file as a Git LFS pointer rather than the real
+/// blob contents (see the [Git LFS pointer spec](https://github.com/git-lfs/git-lfs/blob/main/docs/spec.md)).
+const LFS_POINTER_HEADER: &str = "version https://git-lfs.github.com/spec/v1";
+
+/// Size threshold above which a committed file is considered for
+/// `migrate_to_lfs` (10 MB, matching `LFS_THRESHOLD_BYTES` in `context.rs`).
+const LFS_MIGRATE_THRESHOLD_BYTES: u64 = 10_485_760;
+
+/// Append `pattern filter=lfs diff=lfs merge=lfs -text` to `.gitattributes`
+/// under `project_path`, if it isn't already present.
+fn add_gitattributes_rule(project_path: &Path, pattern: &str) -> Result<(), String> {
+    let gitattributes_path = project_path.join(".gitattributes");
+    let mut existing = fs::read_to_string(&gitattributes_path).unwrap_or_default();
+
+    let rule = format!("{pattern} filter=lfs diff=lfs merge=lfs -text");
+    if !existing.lines().any(|line| line.trim() == rule) {
+        if !existing.is_empty() && !existing.ends_with('\n') {
+            existing.push('\n');
+        }
+        existing.push_str(&rule);
+        existing.push('\n');
+        fs::write(&gitattributes_path, existing)
+            .map_err(|e| format!("Failed to update .gitattributes: {e}"))?;
+    }
+
+    Ok(())
+}
+
+/// Append `pattern` to `.gitattributes` (if not already present) and run
+/// `git lfs track <pattern>` so new files matching it are tracked by LFS
+/// going forward.
+#[tauri::command]
+#[specta::specta]
+pub fn track_lfs_pattern(project_path: PathBuf, pattern: String) -> Result<(), String> {
+    log::info!("Tracking LFS pattern '{pattern}' in {project_path:?}");
+
+    if pattern.trim().is_empty() {
+        return Err("Pattern cannot be empty".to_string());
+    }
+
+    add_gitattributes_rule(&project_path, &pattern)?;
+
+    let output = Command::new("git")
+        .current_dir(&project_path)
+        .args(["lfs", "track", &pattern])
+        .output()
+        .map_err(|e| format!("Failed to run git lfs track: {e}"))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        log::error!("git lfs track failed: {stderr}");
+        return Err(format!("git lfs track failed: {stderr}"));
+    }
+
+    Ok(())
+}
+
+/// Migrate already-committed files over the LFS size threshold into LFS
+/// pointers via `git lfs migrate import`, returning the paths migrated.
+#[tauri::command]
+#[specta::specta]
+pub fn migrate_to_lfs(project_path: PathBuf) -> Result<Vec<String>, String> {
+    log::info!("Migrating oversized committed files to LFS in {project_path:?}");
+
+    let repo = git2::Repository::open(&project_path)
+        .map_err(|e| format!("Failed to open Git repository: {e}"))?;
+
+    let head_tree = repo
+        .head()
+        .and_then(|head| head.peel_to_tree())
+        .map_err(|e| format!("Failed to resolve HEAD tree: {e}"))?;
+
+    let mut oversized = Vec::new();
+    head_tree
+        .walk(git2::TreeWalkMode::PreOrder, |root, entry| {
+            if entry.kind() == Some(git2::ObjectType::Blob) {
+                if let Some(name) = entry.name() {
+                    if let Ok(object) = entry.to_object(&repo) {
+                        if let Some(blob) = object.as_blob() {
+                            if blob.size() as u64 > LFS_MIGRATE_THRESHOLD_BYTES {
+                                oversized.push(format!("{root}{name}"));
+                            }
+                        }
+                    }
+                }
+            }
+            0
+        })
+        .map_err(|e| format!("Failed to walk HEAD tree: {e}"))?;
+
+    if oversized.is_empty() {
+        log::info!("No committed files over the LFS threshold found");
+        return Ok(oversized);
+    }
+
+    let include = oversized.join(",");
+    let output = Command::new("git")
+        .current_dir(&project_path)
+        .args([
+            "lfs",
+            "migrate",
+            "import",
+            "--yes",
+            &format!("--include={include}"),
+        ])
+        .output()
+        .map_err(|e| format!("Failed to run git lfs migrate: {e}"))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        log::error!("git lfs migrate failed: {stderr}");
+        return Err(format!("git lfs migrate failed: {stderr}"));
+    }
+
+    log::info!("Migrated {} file(s) to LFS", oversized.len());
+    Ok(oversized)
+}
+
+/// The pointer file contents for a blob now held in the local LFS object
+/// store, along with the OID/size used to build it.
+pub struct LfsPointer {
+    pub oid: String,
+    pub size: u64,
+    pub contents: String,
+}
+
+/// Write `content` into `repo_path`'s local LFS object store at
+/// `.git/lfs/objects/<oid[0:2]>/<oid[2:4]>/<oid>`, mirroring the real
+/// `git-lfs` CLI's layout, and return the pointer file that should be
+/// committed in place of the raw bytes.
+pub fn store_lfs_object(repo_path: &Path, content: &[u8]) -> Result<LfsPointer, String> {
+    let mut hasher = Sha256::new();
+    hasher.update(content);
+    let oid: String = hasher
+        .finalize()
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect();
+
+    let object_dir = repo_path
+        .join(".git/lfs/objects")
+        .join(&oid[0..2])
+        .join(&oid[2..4]);
+    fs::create_dir_all(&object_dir)
+        .map_err(|e| format!("Failed to create LFS object directory: {e}"))?;
+
+    let object_path = object_dir.join(&oid);
+    fs::write(&object_path, content).map_err(|e| format!("Failed to write LFS object: {e}"))?;
+
+    let size = content.len() as u64;
+    let contents = format!("{LFS_POINTER_HEADER}\noid sha256:{oid}\nsize {size}\n");
+
+    log::info!("Stored LFS object {oid} ({size} bytes)");
+    Ok(LfsPointer { oid, size, contents })
+}
+
+/// Make sure `relative_path` is tracked by Git LFS in `project_path`'s
+/// `.gitattributes`, adding the rule if it's missing. Unlike
+/// `track_lfs_pattern`, this doesn't shell out to `git lfs track`, since
+/// [`store_lfs_object`] writes the object and pointer itself rather than
+/// relying on the `git-lfs` CLI being installed.
+pub fn ensure_lfs_gitattributes_entry(
+    project_path: &Path,
+    relative_path: &str,
+) -> Result<(), String> {
+    add_gitattributes_rule(project_path, relative_path)
+}
+
+/// Check whether the bytes at `path` are a Git LFS pointer file rather than
+/// the real blob contents, by looking for the pointer spec header.
+pub fn is_lfs_pointer(path: &Path) -> bool {
+    let mut file = match fs::File::open(path) {
+        Ok(file) => file,
+        Err(_) => return false,
+    };
+
+    let mut buf = vec![0u8; LFS_POINTER_HEADER.len()];
+    match file.read_exact(&mut buf) {
+        Ok(()) => buf == LFS_POINTER_HEADER.as_bytes(),
+        Err(_) => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_track_lfs_pattern_appends_rule() {
+        let temp_dir = TempDir::new().unwrap();
+        let project_path = temp_dir.path().to_path_buf();
+        git2::Repository::init(&project_path).unwrap();
+
+        // git lfs may not be installed in this environment; only assert on
+        // the .gitattributes side effect, which happens before the shell-out.
+        let _ = track_lfs_pattern(project_path.clone(), "context/**/*.zip".to_string());
+
+        let gitattributes = fs::read_to_string(project_path.join(".gitattributes")).unwrap();
+        assert!(gitattributes.contains("context/**/*.zip filter=lfs"));
+    }
+
+    #[test]
+    fn test_track_lfs_pattern_rejects_empty() {
+        let temp_dir = TempDir::new().unwrap();
+        let result = track_lfs_pattern(temp_dir.path().to_path_buf(), "".to_string());
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_track_lfs_pattern_does_not_duplicate_rule() {
+        let temp_dir = TempDir::new().unwrap();
+        let project_path = temp_dir.path().to_path_buf();
+        git2::Repository::init(&project_path).unwrap();
+
+        let _ = track_lfs_pattern(project_path.clone(), "*.pdf".to_string());
+        let _ = track_lfs_pattern(project_path.clone(), "*.pdf".to_string());
+
+        let gitattributes = fs::read_to_string(project_path.join(".gitattributes")).unwrap();
+        assert_eq!(gitattributes.matches("*.pdf filter=lfs").count(), 1);
+    }
+
+    #[test]
+    fn test_store_lfs_object_writes_object_and_builds_pointer() {
+        let temp_dir = TempDir::new().unwrap();
+        let repo_path = temp_dir.path().to_path_buf();
+        git2::Repository::init(&repo_path).unwrap();
+
+        let content = b"a very large file, hypothetically";
+        let pointer = store_lfs_object(&repo_path, content).unwrap();
+
+        assert_eq!(pointer.oid.len(), 64);
+        assert_eq!(pointer.size, content.len() as u64);
+        assert!(pointer.contents.starts_with(LFS_POINTER_HEADER));
+        assert!(pointer.contents.contains(&format!("oid sha256:{}", pointer.oid)));
+        assert!(pointer.contents.contains(&format!("size {}", pointer.size)));
+
+        let object_path = repo_path
+            .join(".git/lfs/objects")
+            .join(&pointer.oid[0..2])
+            .join(&pointer.oid[2..4])
+            .join(&pointer.oid);
+        assert_eq!(fs::read(object_path).unwrap(), content);
+    }
+
+    #[test]
+    fn test_store_lfs_object_is_content_addressed() {
+        let temp_dir = TempDir::new().unwrap();
+        let repo_path = temp_dir.path().to_path_buf();
+        git2::Repository::init(&repo_path).unwrap();
+
+        let first = store_lfs_object(&repo_path, b"same content").unwrap();
+        let second = store_lfs_object(&repo_path, b"same content").unwrap();
+        assert_eq!(first.oid, second.oid);
+
+        let third = store_lfs_object(&repo_path, b"different content").unwrap();
+        assert_ne!(first.oid, third.oid);
+    }
+
+    #[test]
+    fn test_ensure_lfs_gitattributes_entry_adds_rule_once() {
+        let temp_dir = TempDir::new().unwrap();
+        let project_path = temp_dir.path().to_path_buf();
+
+        ensure_lfs_gitattributes_entry(&project_path, "context/big-file.csv").unwrap();
+        ensure_lfs_gitattributes_entry(&project_path, "context/big-file.csv").unwrap();
+
+        let gitattributes = fs::read_to_string(project_path.join(".gitattributes")).unwrap();
+        assert_eq!(
+            gitattributes
+                .matches("context/big-file.csv filter=lfs")
+                .count(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_is_lfs_pointer_detects_pointer_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let pointer_path = temp_dir.path().join("large.xlsx");
+        fs::write(
+            &pointer_path,
+            "version https://git-lfs.github.com/spec/v1\noid sha256:abc\nsize 123\n",
+        )
+        .unwrap();
+
+        assert!(is_lfs_pointer(&pointer_path));
+    }
+
+    #[test]
+    fn test_is_lfs_pointer_rejects_real_blob() {
+        let temp_dir = TempDir::new().unwrap();
+        let real_path = temp_dir.path().join("data.csv");
+        fs::write(&real_path, "name,age\nAlice,30\n").unwrap();
+
+        assert!(!is_lfs_pointer(&real_path));
+    }
+
+    #[test]
+    fn test_is_lfs_pointer_handles_missing_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let missing_path = temp_dir.path().join("missing.csv");
+
+        assert!(!is_lfs_pointer(&missing_path));
+    }
+
+    /// Commit a single file into a fresh repository and return its path.
+    fn commit_test_repo_with_file(relative_path: &str, content: &[u8]) -> (TempDir, PathBuf) {
+        let temp_dir = TempDir::new().unwrap();
+        let repo_path = temp_dir.path().to_path_buf();
+        let repo = git2::Repository::init(&repo_path).unwrap();
+
+        let file_path = repo_path.join(relative_path);
+        fs::create_dir_all(file_path.parent().unwrap()).unwrap();
+        fs::write(&file_path, content).unwrap();
+
+        let mut index = repo.index().unwrap();
+        index.add_path(Path::new(relative_path)).unwrap();
+        index.write().unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let signature = git2::Signature::now("Test User", "test@example.com").unwrap();
+        repo.commit(Some("HEAD"), &signature, &signature, "Add big file", &tree, &[])
+            .unwrap();
+
+        (temp_dir, repo_path)
+    }
+
+    #[test]
+    fn test_migrate_to_lfs_detects_oversized_committed_blob() {
+        let content = vec![0u8; (LFS_MIGRATE_THRESHOLD_BYTES + 1) as usize];
+        let (_temp_dir, repo_path) = commit_test_repo_with_file("big.bin", &content);
+
+        // git-lfs may not be installed in this environment; either outcome
+        // confirms the tree walk found the oversized blob and reached the
+        // shell-out -- only a missing/incorrect detection would short-circuit
+        // to `Ok(vec![])` before ever invoking `git lfs migrate`.
+        match migrate_to_lfs(repo_path) {
+            Ok(migrated) => assert_eq!(migrated, vec!["big.bin".to_string()]),
+            Err(e) => assert!(e.contains("git lfs migrate failed"), "unexpected error: {e}"),
+        }
+    }
+
+    #[test]
+    fn test_migrate_to_lfs_no_oversized_files_skips_shell_out() {
+        let (_temp_dir, repo_path) = commit_test_repo_with_file("small.csv", b"a,b\n1,2\n");
+
+        let migrated = migrate_to_lfs(repo_path).unwrap();
+        assert!(migrated.is_empty());
+    }
+}
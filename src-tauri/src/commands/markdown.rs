@@ -0,0 +1,190 @@
+//! Markdown rendering commands.
+//!
+//! Renders project markdown (README, decision records) to sanitized HTML
+//! with syntax-highlighted fenced code blocks, the same approach `rgit` and
+//! `itsy-gitsy` use to turn repo markdown into themed HTML.
+
+use pulldown_cmark::{html, CodeBlockKind, CowStr, Event, Options, Parser, Tag, TagEnd};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+use syntect::html::{ClassStyle, ClassedHTMLGenerator};
+use syntect::parsing::SyntaxSet;
+use syntect::util::LinesWithEndings;
+
+/// Loaded once and shared for the lifetime of the process; building a
+/// `SyntaxSet` from the bundled definitions is relatively expensive.
+static SYNTAX_SET: LazyLock<SyntaxSet> = LazyLock::new(SyntaxSet::load_defaults_newlines);
+
+/// Render a project markdown file (e.g. `README.md`, a `decisions/*.md`
+/// record) to sanitized HTML, with fenced code blocks highlighted via
+/// `syntect` CSS classes so the app's light/dark/system theme
+/// (`AppPreferences::theme`) can style them.
+///
+/// # Arguments
+/// * `project_path` - Path to the project root (Git repository)
+/// * `relative_path` - Path to the markdown file, relative to `project_path`
+#[tauri::command]
+#[specta::specta]
+pub fn render_markdown(project_path: PathBuf, relative_path: String) -> Result<String, String> {
+    log::info!("Rendering markdown: {relative_path} in {project_path:?}");
+
+    let file_path = resolve_safe_path(&project_path, &relative_path)?;
+
+    let content = fs::read_to_string(&file_path).map_err(|e| {
+        log::error!("Failed to read markdown file {file_path:?}: {e}");
+        format!("Failed to read markdown file: {e}")
+    })?;
+
+    Ok(render_markdown_to_html(&content))
+}
+
+/// Resolve `relative_path` against `project_path`, rejecting anything that
+/// would escape the project directory (e.g. `../../etc/passwd`).
+fn resolve_safe_path(project_path: &Path, relative_path: &str) -> Result<PathBuf, String> {
+    let canonical_project = project_path
+        .canonicalize()
+        .map_err(|e| format!("Failed to resolve project path: {e}"))?;
+
+    let candidate = canonical_project.join(relative_path);
+    let canonical_candidate = candidate
+        .canonicalize()
+        .map_err(|e| format!("File does not exist: {e}"))?;
+
+    if !canonical_candidate.starts_with(&canonical_project) {
+        return Err("Path escapes project directory".to_string());
+    }
+
+    Ok(canonical_candidate)
+}
+
+/// Convert markdown to sanitized HTML, highlighting fenced code blocks.
+pub fn render_markdown_to_html(content: &str) -> String {
+    let options = Options::ENABLE_TABLES | Options::ENABLE_STRIKETHROUGH | Options::ENABLE_FOOTNOTES;
+    let parser = Parser::new_ext(content, options);
+
+    let mut events = Vec::new();
+    let mut code_lang: Option<String> = None;
+    let mut code_buffer = String::new();
+
+    for event in parser {
+        match event {
+            Event::Start(Tag::CodeBlock(kind)) => {
+                code_lang = Some(match kind {
+                    CodeBlockKind::Fenced(lang) => lang.to_string(),
+                    CodeBlockKind::Indented => String::new(),
+                });
+                code_buffer.clear();
+            }
+            Event::Text(text) if code_lang.is_some() => {
+                code_buffer.push_str(&text);
+            }
+            Event::End(TagEnd::CodeBlock) => {
+                let lang = code_lang.take().unwrap_or_default();
+                events.push(Event::Html(CowStr::from(highlight_code_block(
+                    &code_buffer,
+                    &lang,
+                ))));
+            }
+            other => events.push(other),
+        }
+    }
+
+    let mut html_output = String::new();
+    html::push_html(&mut html_output, events.into_iter());
+
+    // Strip anything that isn't plain rendered markup (scripts, inline
+    // event handlers, etc.) before handing HTML back to the frontend.
+    ammonia::Builder::default()
+        .add_generic_attributes(&["class"])
+        .clean(&html_output)
+        .to_string()
+}
+
+/// Highlight a fenced code block's contents, emitting `<span>`s with CSS
+/// classes (via `ClassedHTMLGenerator`) rather than inline colors, so the
+/// highlighting follows the active light/dark/system theme.
+fn highlight_code_block(code: &str, lang: &str) -> String {
+    let syntax = SYNTAX_SET
+        .find_syntax_by_token(lang)
+        .unwrap_or_else(|| SYNTAX_SET.find_syntax_plain_text());
+
+    let mut generator =
+        ClassedHTMLGenerator::new_with_class_style(syntax, &SYNTAX_SET, ClassStyle::Spaced);
+
+    for line in LinesWithEndings::from(code) {
+        // Best-effort: a highlighting failure shouldn't fail the whole render.
+        let _ = generator.parse_html_for_line_which_includes_newline(line);
+    }
+
+    format!(
+        "<pre class=\"highlight\"><code>{}</code></pre>",
+        generator.finalize()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_render_markdown_basic() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join("README.md"), "# Hello\n\nWorld.").unwrap();
+
+        let html = render_markdown(
+            temp_dir.path().to_path_buf(),
+            "README.md".to_string(),
+        )
+        .unwrap();
+
+        assert!(html.contains("<h1>Hello</h1>"));
+        assert!(html.contains("<p>World.</p>"));
+    }
+
+    #[test]
+    fn test_render_markdown_highlights_fenced_code() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(
+            temp_dir.path().join("doc.md"),
+            "```rust\nfn main() {}\n```",
+        )
+        .unwrap();
+
+        let html = render_markdown(temp_dir.path().to_path_buf(), "doc.md".to_string()).unwrap();
+
+        assert!(html.contains("class=\"highlight\""));
+    }
+
+    #[test]
+    fn test_render_markdown_sanitizes_script_tags() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(
+            temp_dir.path().join("doc.md"),
+            "Hello <script>alert('xss')</script>",
+        )
+        .unwrap();
+
+        let html = render_markdown(temp_dir.path().to_path_buf(), "doc.md".to_string()).unwrap();
+
+        assert!(!html.contains("<script>"));
+    }
+
+    #[test]
+    fn test_resolve_safe_path_rejects_traversal() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::create_dir_all(temp_dir.path().join("decisions")).unwrap();
+
+        let result = resolve_safe_path(temp_dir.path(), "../../etc/passwd");
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_render_markdown_missing_file() {
+        let temp_dir = TempDir::new().unwrap();
+
+        let result = render_markdown(temp_dir.path().to_path_buf(), "missing.md".to_string());
+        assert!(result.is_err());
+    }
+}
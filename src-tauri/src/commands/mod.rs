@@ -3,10 +3,15 @@
 //! Each submodule contains related commands and their helper functions.
 //! Import specific commands via their submodule (e.g., `commands::preferences::greet`).
 
+pub mod attio;
 pub mod chat;
 pub mod context;
 pub mod decisions;
+pub mod experiments;
 pub mod git;
+pub mod index;
+pub mod lfs;
+pub mod markdown;
 pub mod notifications;
 pub mod preferences;
 pub mod projects;
@@ -2,6 +2,8 @@
 //!
 //! Handles project initialization with Git, LFS detection, and directory structure setup.
 
+use crate::commands::git::{repo_statuses, resolve_init_signature, GitIdentitySource};
+use crate::commands::lfs::is_lfs_pointer;
 use git2::Repository;
 use serde::{Deserialize, Serialize};
 use specta::Type;
@@ -18,6 +20,10 @@ pub struct GitInitResult {
     pub path: String,
     pub lfs_available: bool,
     pub commit_hash: Option<String>,
+    /// Which source the initial commit's author identity came from, so the
+    /// UI can prompt the user to configure their name/email if it fell back
+    /// to the synthetic placeholder.
+    pub identity_source: GitIdentitySource,
 }
 
 /// Initialize a Git repository for a project with proper directory structure.
@@ -159,17 +165,8 @@ Install Git LFS: https://git-lfs.github.com/
         format!("Failed to find tree: {e}")
     })?;
 
-    // Try to get signature from Git config, fall back to default if not configured
-    let signature = repo
-        .signature()
-        .or_else(|_| {
-            log::warn!("Git user not configured, using default signature");
-            git2::Signature::now("Unheard User", "user@unheard.local")
-        })
-        .map_err(|e| {
-            log::error!("Failed to create signature: {e}");
-            format!("Failed to create signature: {e}")
-        })?;
+    // Resolve the commit author: repo/global config first, synthetic last.
+    let (signature, identity_source) = resolve_init_signature(&repo);
 
     let commit_id = repo
         .commit(
@@ -193,6 +190,7 @@ Install Git LFS: https://git-lfs.github.com/
         path: path.to_string_lossy().to_string(),
         lfs_available,
         commit_hash: Some(commit_hash),
+        identity_source,
     })
 }
 
@@ -221,6 +219,48 @@ pub fn detect_git_lfs() -> Result<bool, String> {
     }
 }
 
+/// Per-file Git status, mirroring how tools like `exa`/`lsd` annotate each
+/// directory entry with its repository state.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub enum GitFileStatus {
+    /// Not tracked by Git at all.
+    Untracked,
+    /// Tracked, with working-tree changes not yet staged.
+    Modified,
+    /// Staged in the index but not yet committed.
+    Staged,
+    /// Tracked with no outstanding changes.
+    Committed,
+    /// Excluded via `.gitignore`.
+    Ignored,
+}
+
+impl GitFileStatus {
+    fn from_git2(status: git2::Status) -> Self {
+        if status.is_ignored() {
+            GitFileStatus::Ignored
+        } else if status.is_wt_new() {
+            GitFileStatus::Untracked
+        } else if status.is_index_new()
+            || status.is_index_modified()
+            || status.is_index_deleted()
+            || status.is_index_renamed()
+            || status.is_index_typechange()
+        {
+            GitFileStatus::Staged
+        } else if status.is_wt_modified()
+            || status.is_wt_deleted()
+            || status.is_wt_renamed()
+            || status.is_wt_typechange()
+        {
+            GitFileStatus::Modified
+        } else {
+            GitFileStatus::Committed
+        }
+    }
+}
+
 /// Information about a file in the project directory.
 #[derive(Debug, Clone, Serialize, Deserialize, Type)]
 #[serde(rename_all = "camelCase")]
@@ -230,12 +270,19 @@ pub struct ProjectFile {
     pub extension: String,
     pub size: u64,
     pub is_supported: bool,
+    /// Git status for this path, or `None` if `project_path` isn't inside a
+    /// Git repository.
+    pub git_status: Option<GitFileStatus>,
+    /// `true` if this file is an unresolved Git LFS pointer rather than the
+    /// real blob contents (e.g. LFS wasn't installed when it was checked out).
+    pub is_lfs_pointer: bool,
 }
 
 /// List all files in a project directory recursively.
 ///
 /// Filters for supported file types (CSV, PDF, XLSX, XLS) and returns
-/// file metadata for display and selection.
+/// file metadata for display and selection, decorated with each file's Git
+/// status when `project_path` is inside a Git repository.
 #[tauri::command]
 #[specta::specta]
 pub fn list_project_files(project_path: PathBuf) -> Result<Vec<ProjectFile>, String> {
@@ -252,10 +299,24 @@ pub fn list_project_files(project_path: PathBuf) -> Result<Vec<ProjectFile>, Str
     let supported_extensions = vec!["csv", "pdf", "xlsx", "xls"];
     let mut files = Vec::new();
 
+    // Best-effort: a directory that isn't (yet) a Git repository should
+    // still be listable, just without status annotations.
+    let statuses = repo_statuses(&project_path).ok();
+
+    // `repo_statuses` keys its map by path relative to the repo root found by
+    // `Repository::discover`, which may sit above `project_path` when it's a
+    // subdirectory of the repo -- so status lookups must use that same root,
+    // not `project_path`, or every file would wrongly report as Committed.
+    let repo_root = Repository::discover(&project_path)
+        .ok()
+        .and_then(|repo| repo.workdir().map(|w| w.to_path_buf()));
+
     fn scan_directory(
         dir: &PathBuf,
         base_path: &PathBuf,
+        repo_root: &Option<PathBuf>,
         supported_exts: &[&str],
+        statuses: &Option<std::sync::Arc<std::collections::HashMap<PathBuf, git2::Status>>>,
         files: &mut Vec<ProjectFile>,
     ) -> Result<(), String> {
         let entries = fs::read_dir(dir)
@@ -274,7 +335,7 @@ pub fn list_project_files(project_path: PathBuf) -> Result<Vec<ProjectFile>, Str
 
             if path.is_dir() {
                 // Recursively scan subdirectories
-                scan_directory(&path, base_path, supported_exts, files)?;
+                scan_directory(&path, base_path, repo_root, supported_exts, statuses, files)?;
             } else if path.is_file() {
                 let extension = path
                     .extension()
@@ -301,12 +362,29 @@ pub fn list_project_files(project_path: PathBuf) -> Result<Vec<ProjectFile>, Str
                     .map(|m| m.len())
                     .unwrap_or(0);
 
+                // `statuses` is keyed by path relative to the repo root, which
+                // may differ from `base_path` when `project_path` is a
+                // subdirectory of the repo -- re-derive the lookup key from
+                // `repo_root` rather than reusing `relative_path`.
+                let status_key = repo_root
+                    .as_ref()
+                    .map(|root| path.strip_prefix(root).unwrap_or(&path).to_path_buf())
+                    .unwrap_or_else(|| PathBuf::from(&relative_path));
+
+                let git_status = statuses.as_ref().map(|map| {
+                    map.get(status_key.as_path())
+                        .map(|s| GitFileStatus::from_git2(*s))
+                        .unwrap_or(GitFileStatus::Committed)
+                });
+
                 files.push(ProjectFile {
                     path: relative_path,
                     name,
                     extension,
                     size,
                     is_supported,
+                    git_status,
+                    is_lfs_pointer: is_lfs_pointer(&path),
                 });
             }
         }
@@ -314,7 +392,14 @@ pub fn list_project_files(project_path: PathBuf) -> Result<Vec<ProjectFile>, Str
         Ok(())
     }
 
-    scan_directory(&project_path, &project_path, &supported_extensions, &mut files)?;
+    scan_directory(
+        &project_path,
+        &project_path,
+        &repo_root,
+        &supported_extensions,
+        &statuses,
+        &mut files,
+    )?;
 
     log::info!("Found {} files in project", files.len());
     Ok(files)
@@ -378,4 +463,88 @@ mod tests {
         let head = repo.head().unwrap();
         assert!(head.is_branch());
     }
+
+    #[test]
+    fn test_initialize_git_identity_source_is_not_synthetic_when_configured() {
+        let temp_dir = TempDir::new().unwrap();
+        let project_path = temp_dir.path().to_path_buf();
+
+        let result = initialize_git(project_path).unwrap();
+
+        // CI/dev environments always have a global Git identity configured,
+        // so a fresh init should never need the synthetic placeholder.
+        assert_ne!(result.identity_source, GitIdentitySource::Synthetic);
+    }
+
+    #[test]
+    fn test_list_project_files_reports_git_status() {
+        let temp_dir = TempDir::new().unwrap();
+        let project_path = temp_dir.path().to_path_buf();
+
+        initialize_git(project_path.clone()).unwrap();
+        fs::write(project_path.join("context/new.csv"), "a,b\n1,2").unwrap();
+
+        let files = list_project_files(project_path).unwrap();
+        let new_file = files
+            .iter()
+            .find(|f| f.path == "context/new.csv")
+            .expect("new.csv should be listed");
+
+        assert_eq!(new_file.git_status, Some(GitFileStatus::Untracked));
+    }
+
+    #[test]
+    fn test_list_project_files_reports_git_status_from_subdirectory() {
+        let temp_dir = TempDir::new().unwrap();
+        let project_path = temp_dir.path().to_path_buf();
+
+        initialize_git(project_path.clone()).unwrap();
+        let sub_dir = project_path.join("context/sub");
+        fs::create_dir_all(&sub_dir).unwrap();
+        fs::write(sub_dir.join("new.csv"), "a,b\n1,2").unwrap();
+
+        // Listing from a subdirectory of the repo must still key Git status
+        // lookups off the repo root `Repository::discover` finds, not off
+        // `sub_dir` itself.
+        let files = list_project_files(sub_dir).unwrap();
+        let new_file = files
+            .iter()
+            .find(|f| f.path == "new.csv")
+            .expect("new.csv should be listed");
+
+        assert_eq!(new_file.git_status, Some(GitFileStatus::Untracked));
+    }
+
+    #[test]
+    fn test_list_project_files_detects_lfs_pointer() {
+        let temp_dir = TempDir::new().unwrap();
+        let project_path = temp_dir.path().to_path_buf();
+        fs::create_dir_all(project_path.join("context")).unwrap();
+        fs::write(
+            project_path.join("context/large.xlsx"),
+            "version https://git-lfs.github.com/spec/v1\noid sha256:abc\nsize 123\n",
+        )
+        .unwrap();
+
+        let files = list_project_files(project_path).unwrap();
+        let large_file = files
+            .iter()
+            .find(|f| f.path == "context/large.xlsx")
+            .unwrap();
+
+        assert!(large_file.is_lfs_pointer);
+    }
+
+    #[test]
+    fn test_list_project_files_without_git_repo() {
+        let temp_dir = TempDir::new().unwrap();
+        let project_path = temp_dir.path().to_path_buf();
+        fs::create_dir_all(project_path.join("context")).unwrap();
+        fs::write(project_path.join("context/file.csv"), "a,b\n1,2").unwrap();
+
+        let files = list_project_files(project_path).unwrap();
+        let file = files.first().expect("file should be listed");
+
+        assert_eq!(file.git_status, None);
+    }
 }
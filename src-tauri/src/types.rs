@@ -133,7 +133,8 @@ pub struct ContextFileRecord {
     pub original_filename: String,
     /// Sanitized filename stored in project (slugified)
     pub stored_filename: String,
-    /// File type: "csv", "pdf", or "excel"
+    /// File type: "csv", "pdf", "excel", "markdown", "text", or a detected
+    /// source language (e.g. "rust", "python")
     pub file_type: String,
     /// Detected data type based on content analysis (e.g., "customer_data")
     pub detected_type: Option<String>,
@@ -153,6 +154,50 @@ pub struct ContextFileRecord {
     pub relative_file_path: String,
     /// Whether file exceeds LFS threshold (>10MB)
     pub is_lfs: bool,
+    /// SHA-256 hex digest of the file's contents, used to detect re-uploads
+    /// of the same file under a different name
+    pub content_hash: String,
+    /// Actual file type as classified by magic-byte sniffing, which takes
+    /// precedence over the upload's extension
+    pub sniffed_type: TypeOfFile,
+    /// Result of the structural integrity probe run on the file
+    pub integrity: FileIntegrity,
+    /// Detected CSV field delimiter (e.g. ",", ";", tab, "|"); `None` for
+    /// non-CSV file types
+    pub delimiter: Option<String>,
+    /// Detected text encoding (e.g. "utf-8", "utf-16", "windows-1252");
+    /// `None` for non-CSV file types
+    pub encoding: Option<String>,
+    /// Rendered HTML preview for Markdown (rendered document) and
+    /// recognized source code (syntax-highlighted); `None` when `preview`
+    /// or `text_preview` is used instead
+    pub html_preview: Option<String>,
+}
+
+/// File type classified by magic-byte sniffing rather than trusting the
+/// extension, so e.g. a mislabeled `.csv` that's really a PDF is caught.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub enum TypeOfFile {
+    Csv,
+    Pdf,
+    Excel,
+    Zip,
+    Image,
+    /// Markdown, plain text, or recognized source code
+    Text,
+    Unknown,
+}
+
+/// Result of the structural integrity probe run on an uploaded file (e.g.
+/// whether its ZIP central directory is intact, or its CSV rows are ragged).
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct FileIntegrity {
+    /// Whether the file passed its structural probe
+    pub ok: bool,
+    /// Empty when `ok`; otherwise a human-readable description of the failure
+    pub error_string: String,
 }
 
 /// Progress updates during file upload.
@@ -165,8 +210,14 @@ pub enum UploadProgress {
     Copying { percent: u8 },
     /// Committing to Git
     Committing { percent: u8 },
+    /// Non-fatal issue the frontend should surface (e.g. the file needs
+    /// LFS but LFS isn't installed), upload continues regardless
+    Warning { message: String },
     /// Upload complete with file record
     Complete { record: ContextFileRecord },
+    /// The uploaded file's contents already exist under a different name;
+    /// the existing record was returned instead of creating a duplicate
+    Duplicate { record: ContextFileRecord },
     /// Error occurred
     Error { message: String },
 }
@@ -178,7 +229,6 @@ pub enum UploadProgress {
 /// Git repository status information.
 #[derive(Debug, Clone, Serialize, Deserialize, Type)]
 #[serde(rename_all = "camelCase")]
-#[allow(dead_code)]
 pub struct GitStatus {
     /// Number of uncommitted changes in the repository
     pub uncommitted_changes: usize,